@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use melange::daf::{FileRecord, RECORD_SIZE};
+
+/// A minimal little endian file record
+fn file_record_bytes() -> Vec<u8> {
+    let mut bytes = vec![0; RECORD_SIZE];
+    bytes[0..8].copy_from_slice(b"DAF/SPK ");
+    bytes[8..12].copy_from_slice(&2i32.to_le_bytes());
+    bytes[12..16].copy_from_slice(&6i32.to_le_bytes());
+    bytes[16..76].fill(b' ');
+    bytes[76..80].copy_from_slice(&4i32.to_le_bytes());
+    bytes[80..84].copy_from_slice(&4i32.to_le_bytes());
+    bytes[84..88].copy_from_slice(&1025i32.to_le_bytes());
+    bytes[88..96].copy_from_slice(b"LTL-IEEE");
+    return bytes;
+}
+
+fn bench_file_record(c: &mut Criterion) {
+    let bytes = file_record_bytes();
+    c.bench_function("FileRecord::try_from", |b| {
+        b.iter(|| FileRecord::try_from(black_box(bytes.as_slice())))
+    });
+}
+
+criterion_group!(benches, bench_file_record);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+//! Typed positions within a DAF: record numbers, word addresses and byte offsets
+//!
+//! DAF numbers both records and words (double precision numbers) from 1, while byte offsets
+//! into the file start at 0. Keeping the three apart in the type system prevents mixing up units
+//! and off-by-one errors when converting between them.
+
+use crate::daf::{RECORD_SIZE, WORDS_PER_RECORD, WORD_SIZE};
+
+/// A 1-based physical record number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordNumber(pub i32);
+
+/// A 1-based word address, counting double precision numbers from the start of the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordAddress(pub i32);
+
+/// A 0-based byte offset from the start of the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ByteOffset(pub u64);
+
+impl RecordNumber {
+    /// Byte offset of the start of the record
+    ///
+    /// `None` for non-positive record numbers, such as a zero "no record" link.
+    pub fn byte_offset(self) -> Option<ByteOffset> {
+        if self.0 < 1 {
+            return None;
+        }
+        return Some(ByteOffset((self.0 as u64 - 1) * RECORD_SIZE as u64));
+    }
+
+    /// Address of the first word in the record
+    ///
+    /// Record numbers read from a corrupt file can be anywhere in `i32`; addresses that do not
+    /// fit saturate at the ends of the `i32` range.
+    pub fn first_word(self) -> WordAddress {
+        let word = (self.0 as i64 - 1) * WORDS_PER_RECORD as i64 + 1;
+        return WordAddress(word.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+    }
+}
+
+impl WordAddress {
+    /// Byte offset of the start of the word
+    ///
+    /// `None` for non-positive addresses, which do not name a word.
+    pub fn byte_offset(self) -> Option<ByteOffset> {
+        if self.0 < 1 {
+            return None;
+        }
+        return Some(ByteOffset((self.0 as u64 - 1) * WORD_SIZE as u64));
+    }
+
+    /// The record containing the word
+    pub fn record(self) -> RecordNumber {
+        // Widened so that the full `i32` range works; the result always fits back into `i32`
+        let record = (self.0 as i64 - 1).div_euclid(WORDS_PER_RECORD as i64) + 1;
+        return RecordNumber(record as i32);
+    }
+}
+
+impl ByteOffset {
+    /// The offset as an index into an in-memory buffer, if it fits in `usize`
+    ///
+    /// On 32-bit targets (e.g. wasm32) offsets from large word addresses do not.
+    pub fn as_usize(self) -> Option<usize> {
+        return usize::try_from(self.0).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ByteOffset, RecordNumber, WordAddress};
+
+    #[test]
+    fn record_number() {
+        assert_eq!(Some(ByteOffset(0)), RecordNumber(1).byte_offset());
+        assert_eq!(Some(ByteOffset(3072)), RecordNumber(4).byte_offset());
+        assert_eq!(None, RecordNumber(0).byte_offset());
+        assert_eq!(None, RecordNumber(-1).byte_offset());
+        assert_eq!(WordAddress(1), RecordNumber(1).first_word());
+        assert_eq!(WordAddress(385), RecordNumber(4).first_word());
+    }
+
+    #[test]
+    fn word_address() {
+        assert_eq!(Some(ByteOffset(0)), WordAddress(1).byte_offset());
+        assert_eq!(Some(ByteOffset(1024)), WordAddress(129).byte_offset());
+        assert_eq!(None, WordAddress(0).byte_offset());
+        assert_eq!(RecordNumber(1), WordAddress(128).record());
+        assert_eq!(RecordNumber(2), WordAddress(129).record());
+        assert_eq!(RecordNumber(4), WordAddress(385).record());
+    }
+
+    #[test]
+    fn extreme_values() {
+        assert_eq!(WordAddress(i32::MAX), RecordNumber(i32::MAX).first_word());
+        assert_eq!(WordAddress(i32::MIN), RecordNumber(i32::MIN).first_word());
+        assert_eq!(RecordNumber(-16777216), WordAddress(i32::MIN).record());
+        assert_eq!(RecordNumber(16777216), WordAddress(i32::MAX).record());
+        assert_eq!(RecordNumber(0), WordAddress(0).record());
+        assert_eq!(None, RecordNumber(i32::MIN).byte_offset());
+        assert_eq!(None, WordAddress(i32::MIN).byte_offset());
+        assert_eq!(
+            Some(ByteOffset((i32::MAX as u64 - 1) * 1024)),
+            RecordNumber(i32::MAX).byte_offset()
+        );
+        assert_eq!(
+            Some(ByteOffset((i32::MAX as u64 - 1) * 8)),
+            WordAddress(i32::MAX).byte_offset()
+        );
+    }
+
+    #[test]
+    fn as_usize() {
+        assert_eq!(Some(3072), ByteOffset(3072).as_usize());
+        let large = ByteOffset(u64::MAX);
+        assert_eq!(usize::try_from(u64::MAX).ok(), large.as_usize());
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in 1..10 {
+            let record = RecordNumber(n);
+            assert_eq!(record, record.first_word().record());
+            assert_eq!(record.byte_offset(), record.first_word().byte_offset());
+        }
+    }
+}
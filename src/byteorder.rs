@@ -1,9 +1,19 @@
 //! Utilities for handling file byte ordering at runtime
 
+use std::str::FromStr;
+
+use crate::error::DafError;
+
+/// String indicating that the file is little endian
+const LITTLE_ENDIAN_STRING: &str = "LTL-IEEE";
+
+/// String indicating that the file is big endian
+const BIG_ENDIAN_STRING: &str = "BIG-IEEE";
+
 /// Representation of endianness
 ///
 /// Endiannes is the order of bytes in a word of digital data
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ByteOrder {
     /// Least significant byte of a word at the smallest memory address
     LittleEndian,
@@ -13,6 +23,28 @@ pub enum ByteOrder {
 }
 
 impl ByteOrder {
+    /// The canonical 8 character binary format string used in DAF file records
+    pub fn as_format_string(&self) -> &'static str {
+        return match self {
+            ByteOrder::LittleEndian => LITTLE_ENDIAN_STRING,
+            ByteOrder::BigEndian => BIG_ENDIAN_STRING,
+        };
+    }
+
+    /// The byte order of the host this crate was compiled for
+    pub fn native() -> ByteOrder {
+        if cfg!(target_endian = "big") {
+            return ByteOrder::BigEndian;
+        } else {
+            return ByteOrder::LittleEndian;
+        }
+    }
+
+    /// Whether values in this byte order can be read without swapping bytes on this host
+    pub fn is_native(&self) -> bool {
+        return *self == ByteOrder::native();
+    }
+
     pub fn i32_from_bytes(&self, bytes: &[u8]) -> i32 {
         let mut buf = [0; std::mem::size_of::<i32>()];
         buf.copy_from_slice(&bytes[0..std::mem::size_of::<i32>()]);
@@ -21,4 +53,122 @@ impl ByteOrder {
             ByteOrder::BigEndian => i32::from_be_bytes(buf),
         };
     }
+
+    pub fn f64_from_bytes(&self, bytes: &[u8]) -> f64 {
+        let mut buf = [0; std::mem::size_of::<f64>()];
+        buf.copy_from_slice(&bytes[0..std::mem::size_of::<f64>()]);
+        return match self {
+            ByteOrder::LittleEndian => f64::from_le_bytes(buf),
+            ByteOrder::BigEndian => f64::from_be_bytes(buf),
+        };
+    }
+}
+
+impl FromStr for ByteOrder {
+    type Err = DafError;
+
+    /// Parse a DAF binary format string (`LTL-IEEE`/`BIG-IEEE`) or `little`/`big`
+    ///
+    /// Unknown strings are reported like an unknown format field, with the first 8 bytes of the
+    /// string padded with blanks.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s {
+            LITTLE_ENDIAN_STRING | "little" => Ok(ByteOrder::LittleEndian),
+            BIG_ENDIAN_STRING | "big" => Ok(ByteOrder::BigEndian),
+            _ => {
+                let mut fmt_string = [b' '; 8];
+                let len = s.len().min(fmt_string.len());
+                fmt_string[..len].copy_from_slice(&s.as_bytes()[..len]);
+                Err(DafError::InvalidFormatString(fmt_string))
+            }
+        };
+    }
+}
+
+impl TryFrom<[u8; 8]> for ByteOrder {
+    type Error = DafError;
+
+    /// Identify the byte order from the 8 byte binary format field of a DAF file record
+    fn try_from(fmt_string: [u8; 8]) -> Result<Self, Self::Error> {
+        if fmt_string == LITTLE_ENDIAN_STRING.as_bytes() {
+            return Ok(ByteOrder::LittleEndian);
+        } else if fmt_string == BIG_ENDIAN_STRING.as_bytes() {
+            return Ok(ByteOrder::BigEndian);
+        }
+        return Err(DafError::InvalidFormatString(fmt_string));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteOrder;
+    use crate::error::DafError;
+
+    #[test]
+    fn from_str() {
+        assert!(matches!("LTL-IEEE".parse(), Ok(ByteOrder::LittleEndian)));
+        assert!(matches!("BIG-IEEE".parse(), Ok(ByteOrder::BigEndian)));
+        assert!(matches!("little".parse(), Ok(ByteOrder::LittleEndian)));
+        assert!(matches!("big".parse(), Ok(ByteOrder::BigEndian)));
+    }
+
+    #[test]
+    fn from_str_unknown() {
+        assert_eq!(
+            Err(DafError::InvalidFormatString(*b"VAX-GFLT")),
+            "VAX-GFLT".parse::<ByteOrder>()
+        );
+        assert_eq!(
+            Err(DafError::InvalidFormatString(*b"ltl-ieee")),
+            "ltl-ieee".parse::<ByteOrder>()
+        );
+        assert_eq!(
+            Err(DafError::InvalidFormatString(*b"LTL     ")),
+            "LTL".parse::<ByteOrder>()
+        );
+        assert_eq!(
+            Err(DafError::InvalidFormatString(*b"LTL-IEEE")),
+            "LTL-IEEE-EXTRA".parse::<ByteOrder>()
+        );
+    }
+
+    #[test]
+    fn as_format_string() {
+        let ltl = ByteOrder::LittleEndian.as_format_string();
+        let big = ByteOrder::BigEndian.as_format_string();
+        assert_eq!("LTL-IEEE", ltl);
+        assert_eq!("BIG-IEEE", big);
+        assert!(matches!(ltl.parse(), Ok(ByteOrder::LittleEndian)));
+        assert!(matches!(big.parse(), Ok(ByteOrder::BigEndian)));
+    }
+
+    #[test]
+    fn native() {
+        let native = ByteOrder::native();
+        assert!(native.is_native());
+        assert_eq!(
+            cfg!(target_endian = "little"),
+            ByteOrder::LittleEndian.is_native()
+        );
+        assert_eq!(
+            cfg!(target_endian = "big"),
+            ByteOrder::BigEndian.is_native()
+        );
+        assert_eq!(1.5, native.f64_from_bytes(&1.5f64.to_ne_bytes()));
+    }
+
+    #[test]
+    fn try_from_format_field() {
+        assert_eq!(
+            Ok(ByteOrder::LittleEndian),
+            ByteOrder::try_from(*b"LTL-IEEE")
+        );
+        assert_eq!(Ok(ByteOrder::BigEndian), ByteOrder::try_from(*b"BIG-IEEE"));
+        assert_eq!(
+            Err(DafError::InvalidFormatString([
+                0xde, 0xad, 0xbe, 0xef, 0, 1, 2, 3
+            ])),
+            ByteOrder::try_from([0xde, 0xad, 0xbe, 0xef, 0, 1, 2, 3])
+        );
+    }
 }
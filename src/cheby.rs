@@ -0,0 +1,205 @@
+//! Evaluation of Chebyshev polynomial series
+//!
+//! A series is given by its coefficients `c[0..n]` and evaluated at `x` in `[-1, 1]` as
+//! `sum(c[k] * T_k(x))`. Derivatives are with respect to `x`; callers scale them by the interval
+//! radius to get derivatives with respect to time.
+
+/// Allowed excursion of a scaled argument outside of `[-1, 1]` from rounding
+const INTERVAL_EPSILON: f64 = 1e-10;
+
+/// Map an epoch into the Chebyshev argument `x = (et - midpoint) / radius`
+///
+/// Debug builds assert that `et` lies within the interval, i.e. that `x` is in `[-1, 1]` up to
+/// rounding.
+pub fn interval_fraction(et: f64, midpoint: f64, radius: f64) -> f64 {
+    let x = (et - midpoint) / radius;
+    debug_assert!(
+        x.abs() <= 1.0 + INTERVAL_EPSILON,
+        "epoch {et} outside of Chebyshev interval {midpoint} +/- {radius}"
+    );
+    return x;
+}
+
+/// Value of the Chebyshev series at `x`
+pub fn eval(coeffs: &[f64], x: f64) -> f64 {
+    let mut sum = 0.0;
+    let (mut t_prev, mut t) = (0.0, 1.0);
+    for (k, c) in coeffs.iter().enumerate() {
+        sum += c * t;
+        let t_next = if k == 0 { x } else { 2.0 * x * t - t_prev };
+        t_prev = t;
+        t = t_next;
+    }
+    return sum;
+}
+
+/// Value of the Chebyshev series at `x` using Clenshaw's recurrence
+///
+/// This gives the same result as [`eval`] without forming the basis polynomials, and is better
+/// conditioned for high degree series.
+pub fn eval_clenshaw(coeffs: &[f64], x: f64) -> f64 {
+    let (mut b1, mut b2) = (0.0, 0.0);
+    for c in coeffs.iter().skip(1).rev() {
+        (b1, b2) = (c + 2.0 * x * b1 - b2, b1);
+    }
+    return match coeffs.first() {
+        Some(c0) => c0 + x * b1 - b2,
+        None => 0.0,
+    };
+}
+
+/// Value of the Chebyshev series at `x` using compensated (Neumaier) summation
+///
+/// The terms are the same as in [`eval`], but the rounding error of each addition is carried
+/// along and added back at the end. This matters when large terms cancel, as in high degree
+/// series with large coefficients.
+pub fn eval_compensated(coeffs: &[f64], x: f64) -> f64 {
+    let (mut sum, mut compensation) = (0.0_f64, 0.0);
+    let (mut t_prev, mut t) = (0.0, 1.0);
+    for (k, c) in coeffs.iter().enumerate() {
+        let term = c * t;
+        let next = sum + term;
+        if sum.abs() >= term.abs() {
+            compensation += (sum - next) + term;
+        } else {
+            compensation += (term - next) + sum;
+        }
+        sum = next;
+        let t_next = if k == 0 { x } else { 2.0 * x * t - t_prev };
+        t_prev = t;
+        t = t_next;
+    }
+    return sum + compensation;
+}
+
+/// First derivative with respect to `x` of the Chebyshev series at `x`
+pub fn eval_derivative(coeffs: &[f64], x: f64) -> f64 {
+    let mut sum = 0.0;
+    let (mut t_prev, mut t) = (0.0, 1.0);
+    let (mut dt_prev, mut dt) = (0.0, 0.0);
+    for (k, c) in coeffs.iter().enumerate() {
+        sum += c * dt;
+        let (t_next, dt_next) = if k == 0 {
+            (x, 1.0)
+        } else {
+            (2.0 * x * t - t_prev, 2.0 * t + 2.0 * x * dt - dt_prev)
+        };
+        (t_prev, t) = (t, t_next);
+        (dt_prev, dt) = (dt, dt_next);
+    }
+    return sum;
+}
+
+/// Second derivative with respect to `x` of the Chebyshev series at `x`
+pub fn eval_second_derivative(coeffs: &[f64], x: f64) -> f64 {
+    let mut sum = 0.0;
+    let (mut t_prev, mut t) = (0.0, 1.0);
+    let (mut dt_prev, mut dt) = (0.0, 0.0);
+    let (mut ddt_prev, mut ddt) = (0.0, 0.0);
+    for (k, c) in coeffs.iter().enumerate() {
+        sum += c * ddt;
+        let (t_next, dt_next, ddt_next) = if k == 0 {
+            (x, 1.0, 0.0)
+        } else {
+            (
+                2.0 * x * t - t_prev,
+                2.0 * t + 2.0 * x * dt - dt_prev,
+                4.0 * dt + 2.0 * x * ddt - ddt_prev,
+            )
+        };
+        (t_prev, t) = (t, t_next);
+        (dt_prev, dt) = (dt, dt_next);
+        (ddt_prev, ddt) = (ddt, ddt_next);
+    }
+    return sum;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        eval, eval_clenshaw, eval_compensated, eval_derivative, eval_second_derivative,
+        interval_fraction,
+    };
+    use rand::prelude::*;
+
+    const COEFFS: [f64; 7] = [1.5, -0.75, 0.3, 0.125, -0.06, 0.02, -0.005];
+
+    #[test]
+    fn interval_fraction_mapping() {
+        let (mid, radius) = (86400.0, 43200.0);
+        assert_eq!(-1.0, interval_fraction(43200.0, mid, radius));
+        assert_eq!(0.0, interval_fraction(86400.0, mid, radius));
+        assert_eq!(1.0, interval_fraction(129600.0, mid, radius));
+        assert_eq!(0.5, interval_fraction(108000.0, mid, radius));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn interval_fraction_outside() {
+        interval_fraction(130000.0, 86400.0, 43200.0);
+    }
+
+    #[test]
+    fn low_order_polynomials() {
+        // T_2(x) = 2x^2 - 1, T_3(x) = 4x^3 - 3x
+        let x = 0.3;
+        assert!((eval(&[0.0, 0.0, 1.0], x) - (2.0 * x * x - 1.0)).abs() < 1e-15);
+        assert!((eval(&[0.0, 0.0, 0.0, 1.0], x) - (4.0 * x * x * x - 3.0 * x)).abs() < 1e-15);
+        assert!((eval_derivative(&[0.0, 0.0, 0.0, 1.0], x) - (12.0 * x * x - 3.0)).abs() < 1e-15);
+        assert!((eval_second_derivative(&[0.0, 0.0, 0.0, 1.0], x) - 24.0 * x).abs() < 1e-14);
+        assert_eq!(4.0, eval_second_derivative(&[0.0, 0.0, 1.0], x));
+    }
+
+    #[test]
+    fn clenshaw_matches_naive() {
+        assert_eq!(0.0, eval_clenshaw(&[], 0.5));
+        assert_eq!(2.5, eval_clenshaw(&[2.5], 0.5));
+
+        let mut rng = rand::thread_rng();
+        for degree in [1, 2, 5, 12, 30] {
+            let coeffs: Vec<f64> = (0..=degree).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            for &x in &[-1.0, -0.7, 0.0, 0.33, 1.0] {
+                let naive = eval(&coeffs, x);
+                assert!((eval_clenshaw(&coeffs, x) - naive).abs() < 1e-13);
+            }
+        }
+    }
+
+    #[test]
+    fn compensated_matches_naive() {
+        for &x in &[-1.0, -0.4, 0.0, 0.6, 1.0] {
+            let naive = eval(&COEFFS, x);
+            assert!((eval_compensated(&COEFFS, x) - naive).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn compensated_cancellation() {
+        // At x = 1 every T_k is exactly 1, so the series sums its coefficients: exactly 20 here
+        let coeffs: Vec<f64> = [1e16, 1.0, -1e16, 1.0].repeat(10);
+        let naive = eval(&coeffs, 1.0);
+        let compensated = eval_compensated(&coeffs, 1.0);
+        assert_eq!(20.0, compensated);
+        assert!((naive - 20.0).abs() > 1.0);
+    }
+
+    #[test]
+    fn derivative_matches_finite_difference() {
+        let h = 1e-6;
+        for &x in &[-0.9, -0.2, 0.0, 0.45, 0.8] {
+            let fd = (eval(&COEFFS, x + h) - eval(&COEFFS, x - h)) / (2.0 * h);
+            assert!((eval_derivative(&COEFFS, x) - fd).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn second_derivative_matches_finite_difference() {
+        let h = 1e-6;
+        for &x in &[-0.9, -0.2, 0.0, 0.45, 0.8] {
+            let fd =
+                (eval_derivative(&COEFFS, x + h) - eval_derivative(&COEFFS, x - h)) / (2.0 * h);
+            assert!((eval_second_derivative(&COEFFS, x) - fd).abs() < 1e-7);
+        }
+    }
+}
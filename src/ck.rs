@@ -0,0 +1,125 @@
+//! Definition of types for handling C-kernel (CK) attitude segments
+
+use crate::address::WordAddress;
+use crate::daf::Summary;
+use crate::error::DafError;
+
+/// The largest CK data type documented by NAIF
+const MAX_CK_TYPE: i32 = 6;
+
+/// Description of a single CK segment, decoded from its DAF array summary
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CkSegmentDescriptor {
+    /// NAIF ID of the instrument or structure whose attitude the segment describes
+    pub instrument: i32,
+
+    /// NAIF ID of the reference frame the attitude is relative to
+    pub frame: i32,
+
+    /// CK data type of the segment
+    pub data_type: i32,
+
+    /// Whether the segment stores angular velocity as well as orientation
+    pub has_angular_velocity: bool,
+
+    /// Start of the coverage window (encoded spacecraft clock ticks)
+    pub start_sclk: f64,
+
+    /// End of the coverage window (encoded spacecraft clock ticks)
+    pub stop_sclk: f64,
+
+    /// Word address of the first element of the segment data
+    pub start_addr: WordAddress,
+
+    /// Word address of the last element of the segment data
+    pub end_addr: WordAddress,
+}
+
+impl CkSegmentDescriptor {
+    /// Decode a CK summary (ND = 2, NI = 6)
+    ///
+    /// CK summaries have the same shape as SPK summaries, so the component counts alone cannot
+    /// tell the two apart; the caller knows which kind of file it is reading.
+    pub fn from_summary(summary: &Summary) -> Result<Self, DafError> {
+        let ([start_sclk, stop_sclk], [instrument, frame, data_type, rates, start_addr, end_addr]) =
+            summary.as_spk().ok_or(DafError::SummaryShapeMismatch {
+                n_double: summary.doubles.len(),
+                n_integer: summary.integers.len(),
+            })?;
+
+        if !(1..=MAX_CK_TYPE).contains(&data_type) {
+            return Err(DafError::ImplausibleCkType(data_type));
+        }
+
+        return Ok(CkSegmentDescriptor {
+            instrument,
+            frame,
+            data_type,
+            has_angular_velocity: rates != 0,
+            start_sclk,
+            stop_sclk,
+            start_addr: WordAddress(start_addr),
+            end_addr: WordAddress(end_addr),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CkSegmentDescriptor;
+    use crate::address::WordAddress;
+    use crate::daf::Summary;
+    use crate::error::DafError;
+
+    fn ck_summary(data_type: i32, rates: i32) -> Summary {
+        return Summary {
+            doubles: vec![2.5e10, 2.6e10],
+            integers: vec![-82000, 1, data_type, rates, 641, 9000],
+        };
+    }
+
+    #[test]
+    fn from_summary() {
+        let descriptor = CkSegmentDescriptor::from_summary(&ck_summary(3, 1)).unwrap();
+        assert_eq!(-82000, descriptor.instrument);
+        assert_eq!(1, descriptor.frame);
+        assert_eq!(3, descriptor.data_type);
+        assert!(descriptor.has_angular_velocity);
+        assert_eq!(2.5e10, descriptor.start_sclk);
+        assert_eq!(2.6e10, descriptor.stop_sclk);
+        assert_eq!(WordAddress(641), descriptor.start_addr);
+        assert_eq!(WordAddress(9000), descriptor.end_addr);
+
+        let descriptor = CkSegmentDescriptor::from_summary(&ck_summary(3, 0)).unwrap();
+        assert!(!descriptor.has_angular_velocity);
+    }
+
+    #[test]
+    fn implausible_type() {
+        assert_eq!(
+            Err(DafError::ImplausibleCkType(0)),
+            CkSegmentDescriptor::from_summary(&ck_summary(0, 0))
+        );
+        assert_eq!(
+            Err(DafError::ImplausibleCkType(7)),
+            CkSegmentDescriptor::from_summary(&ck_summary(7, 0))
+        );
+        assert!(CkSegmentDescriptor::from_summary(&ck_summary(6, 0)).is_ok());
+    }
+
+    #[test]
+    fn wrong_shape() {
+        let summary = Summary {
+            doubles: vec![2.5e10],
+            integers: vec![-82000, 1, 3, 1, 641, 9000],
+        };
+        assert_eq!(
+            Err(DafError::SummaryShapeMismatch {
+                n_double: 1,
+                n_integer: 6
+            }),
+            CkSegmentDescriptor::from_summary(&summary)
+        );
+    }
+}
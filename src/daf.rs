@@ -1,6 +1,34 @@
 //! Definition of types and traits for handling Double Precision Array Files
 
+use std::borrow::Cow;
+
+use crate::address::{ByteOffset, RecordNumber, WordAddress};
 use crate::byteorder::ByteOrder;
+use crate::error::DafError;
+
+/// The length (in bytes) of a single physical record in a DAF
+pub const RECORD_SIZE: usize = 1024;
+
+/// The length (in bytes) of a single word (double precision number) in a DAF
+pub const WORD_SIZE: usize = std::mem::size_of::<f64>();
+
+/// The number of words in a single physical record
+pub const WORDS_PER_RECORD: usize = RECORD_SIZE / WORD_SIZE;
+
+/// The number of control words (next, previous, count) at the start of a summary record
+const SUMMARY_CONTROL_WORDS: usize = 3;
+
+/// The number of words available for summaries in a single summary record
+const SUMMARY_RECORD_CAPACITY: i32 = (WORDS_PER_RECORD - SUMMARY_CONTROL_WORDS) as i32;
+
+/// The number of comment characters stored in each comment record (the rest is unused)
+const COMMENT_CHARS_PER_RECORD: usize = 1000;
+
+/// End-of-line marker in the comment area
+const COMMENT_EOL: u8 = 0x00;
+
+/// End-of-comments marker in the comment area
+const COMMENT_EOT: u8 = 0x04;
 
 /// The length (in bytes) of the ID word in the DAF File Record
 const ID_WORD_LENGTH: usize = 8;
@@ -11,13 +39,7 @@ const DESCRIPTION_LENGTH: usize = 60;
 /// The length (in bytes) of the FTP validation string in the DAF File Record
 const FTP_STRING_LENGTH: usize = 28;
 
-/// String indicating that the file is little endian
-const LITTLE_ENDIAN_STRING: &str = "LTL-IEEE";
-
-/// String indicating that the file is big endian
-const BIG_ENDIAN_STRING: &str = "BIG-IEEE";
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FileRecord {
     /// An identification word (`DAF/xxxx')
     pub id_word: [u8; ID_WORD_LENGTH],
@@ -32,16 +54,16 @@ pub struct FileRecord {
     pub description: [u8; DESCRIPTION_LENGTH],
 
     /// The record number of the initial summary record in the file
-    pub forward: i32,
+    pub forward: RecordNumber,
 
     /// The record number of the final summary record in the file
-    pub backward: i32,
+    pub backward: RecordNumber,
 
     /// The first free address in the file
     ///
     /// This is the address at which the first element of the next array to be added to the file
     /// will be stored..
-    pub first_free: i32,
+    pub first_free: WordAddress,
 
     /// The indicatation of the numeric binary format of the DAF
     pub byte_ordering: ByteOrder,
@@ -52,37 +74,205 @@ pub struct FileRecord {
 
 impl FileRecord {
     /// Size of a single summary within a summary record in the DAF file
+    ///
+    /// This and the other layout sizes assume valid ND and NI, which [`FileRecord::try_from`]
+    /// guarantees for parsed records.
     pub fn single_summary_size(&self) -> i32 {
         return self.n_double + (self.n_integer + 1) / 2;
     }
 
     /// Number of characters in a single name in a name record in the DAF file
     pub fn n_character(&self) -> i32 {
-        return 8 * self.single_summary_size();
+        return WORD_SIZE as i32 * self.single_summary_size();
     }
 
     /// Number of summaries within a single summary record
     pub fn summaries_per_record(&self) -> i32 {
-        return 125 / self.single_summary_size();
+        return SUMMARY_RECORD_CAPACITY / self.single_summary_size();
+    }
+
+    /// The identification word as text, without trailing padding (e.g. `DAF/SPK`)
+    pub fn id_word_str(&self) -> Cow<'_, str> {
+        return String::from_utf8_lossy(trim_padding(&self.id_word));
+    }
+
+    /// The internal name or description as text, without trailing padding
+    ///
+    /// Descriptions are padded with spaces, so an all-space description is empty. The first NUL
+    /// is treated as a C string terminator: some writers store separate metadata after it.
+    pub fn description_str(&self) -> Cow<'_, str> {
+        return String::from_utf8_lossy(trim_padding(self.description_text()));
+    }
+
+    /// Whether the description text is entirely printable ASCII
+    ///
+    /// When this is false [`FileRecord::description_str`] would contain replacement characters,
+    /// so tools may prefer to show the raw bytes instead.
+    pub fn description_is_printable(&self) -> bool {
+        return self
+            .description_text()
+            .iter()
+            .all(|&b| b.is_ascii_graphic() || b == b' ');
+    }
+
+    /// The raw FTP validation string (bytes 699 to 727 of the file record)
+    ///
+    /// A file transferred in text mode has this sequence mangled, so the raw bytes show what the
+    /// transfer did to it.
+    pub fn ftp_bytes(&self) -> &[u8; FTP_STRING_LENGTH] {
+        return &self.ftp_string;
+    }
+
+    /// Best-effort name of the planetary ephemeris described by the file (e.g. `DE440`)
+    ///
+    /// Recognizes JPL Development Ephemerides (`DE440`, also in the `DE-0440LE-0440` form),
+    /// IMCCE `INPOP19a` and IAA `EPM2021` identifiers anywhere in the description. Returns
+    /// `None` when no identifier is found; this is a heuristic, not part of the DAF format.
+    pub fn ephemeris_name(&self) -> Option<String> {
+        let text = self.description_str();
+        return text
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .find_map(ephemeris_identifier);
+    }
+
+    /// The description bytes up to the first NUL
+    fn description_text(&self) -> &[u8] {
+        return match self.description.iter().position(|&b| b == 0) {
+            Some(nul) => &self.description[..nul],
+            None => &self.description[..],
+        };
     }
+
+    /// Number of records in a complete file with this record
+    ///
+    /// `first_free` is the 1-based word address after the last word in use, so the file extends
+    /// to the end of the record holding word `first_free - 1`. That record is usually only
+    /// partially filled; the words after the last one in use are unused but still present. A
+    /// `first_free` of 1 or less means no words are in use.
+    pub fn num_records(&self) -> i32 {
+        if self.first_free.0 <= 1 {
+            return 0;
+        }
+        return WordAddress(self.first_free.0 - 1).record().0;
+    }
+
+    /// Minimum size (in bytes) of a complete file with this record
+    ///
+    /// This is [`FileRecord::num_records`] whole records. The file may be larger (trailing free
+    /// space) but a smaller file is truncated.
+    pub fn expected_min_bytes(&self) -> u64 {
+        return self.num_records() as u64 * RECORD_SIZE as u64;
+    }
+
+    /// Number of reserved records between the file record and the first summary record
+    ///
+    /// The file record is record 1 and `forward` is the record number of the first summary
+    /// record, so records `2..forward` are reserved, giving `forward - 2` of them.
+    pub fn n_reserved_records(&self) -> i32 {
+        return self.forward.0.saturating_sub(2).max(0);
+    }
+
+    /// Number of records in the comment area
+    ///
+    /// The DAF comment area is stored in the reserved records, so this is the same as
+    /// [`FileRecord::n_reserved_records`]; a file without comments has none.
+    pub fn n_comment_records(&self) -> i32 {
+        return self.n_reserved_records();
+    }
+
+    /// Whether every numeric read from the file has to swap bytes on this host
+    pub fn requires_byte_swap(&self) -> bool {
+        return !self.byte_ordering.is_native();
+    }
+}
+
+/// Strip trailing space and NUL padding from a fixed-width character field
+fn trim_padding(bytes: &[u8]) -> &[u8] {
+    let end = bytes
+        .iter()
+        .rposition(|&b| b != b' ' && b != 0)
+        .map_or(0, |i| i + 1);
+    return &bytes[..end];
+}
+
+/// The ephemeris identifier at the start of a description word, in its conventional form
+fn ephemeris_identifier(word: &str) -> Option<String> {
+    let upper = word.to_ascii_uppercase();
+    let leading_digits =
+        |rest: &str| rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    if let Some(rest) = upper.strip_prefix("DE") {
+        let rest = rest.strip_prefix('-').unwrap_or(rest);
+        let digits = &rest[..leading_digits(rest)];
+        let number = digits.trim_start_matches('0');
+        if (3..=4).contains(&digits.len()) && number.len() == 3 {
+            return Some(format!("DE{number}"));
+        }
+    } else if let Some(rest) = upper.strip_prefix("INPOP") {
+        let n = leading_digits(rest);
+        let version = rest[n..].chars().next().filter(char::is_ascii_alphabetic);
+        if n == 2 {
+            let version = version.map_or(String::new(), |v| v.to_ascii_lowercase().to_string());
+            return Some(format!("INPOP{}{version}", &rest[..n]));
+        }
+    } else if let Some(rest) = upper.strip_prefix("EPM") {
+        if leading_digits(rest) == 4 {
+            return Some(format!("EPM{}", &rest[..4]));
+        }
+    }
+    return None;
+}
+
+/// Whether the bytes are all printable ASCII text or whitespace
+fn is_text(bytes: &[u8]) -> bool {
+    return bytes
+        .iter()
+        .all(|&b| b.is_ascii_graphic() || b.is_ascii_whitespace());
+}
+
+/// Whether a file that failed to parse as a DAF looks like a text kernel instead
+///
+/// Text kernels start with a `KPL/` tag; anything else that is printable but is not a DAF id word
+/// is treated the same way. A DAF id word with an unsupported format (e.g. `VAX-GFLT`) is not.
+fn looks_like_text_kernel(id_word: &[u8], fmt_string: &[u8]) -> bool {
+    if id_word.starts_with(b"KPL/") {
+        return true;
+    }
+    let is_daf = id_word.starts_with(b"DAF/") || id_word.starts_with(b"NAIF/DAF");
+    return !is_daf && is_text(id_word) && is_text(fmt_string);
 }
 
 /// Validate the values for the number of double and integer components in the array summaries
+///
+/// The ranges are checked first, so that the summary size is only computed when it cannot
+/// overflow.
 fn valid_nd_ni(nd: i32, ni: i32) -> bool {
-    let check_1 = (nd + (ni + 1) / 2) <= 125;
-    let check_2 = (0 <= nd) && (nd <= 124);
-    let check_3 = (2 <= ni) && (ni <= 250);
-    return check_1 && check_2 && check_3;
+    let check_1 = (0..=124).contains(&nd);
+    let check_2 = (2..=250).contains(&ni);
+    return check_1 && check_2 && (nd + (ni + 1) / 2) <= SUMMARY_RECORD_CAPACITY;
 }
 
 impl TryFrom<&[u8]> for FileRecord {
-    type Error = &'static str;
+    type Error = DafError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
-        if bytes.len() < 1024 {
-            return Err("byte buffer too short to parse file record");
-        }
+        let record: &[u8; RECORD_SIZE] = match bytes.get(..RECORD_SIZE) {
+            Some(record) => record.try_into().unwrap(),
+            None => {
+                return Err(DafError::BufferTooShort {
+                    needed: RECORD_SIZE,
+                    actual: bytes.len(),
+                })
+            }
+        };
+        return FileRecord::try_from(record);
+    }
+}
 
+impl TryFrom<&[u8; RECORD_SIZE]> for FileRecord {
+    type Error = DafError;
+
+    fn try_from(bytes: &[u8; RECORD_SIZE]) -> Result<Self, Self::Error> {
         let mut id_word = [0; ID_WORD_LENGTH];
         let mut description = [0; DESCRIPTION_LENGTH];
         let mut fmt_string = [0; 8];
@@ -93,19 +283,26 @@ impl TryFrom<&[u8]> for FileRecord {
         fmt_string.copy_from_slice(&bytes[88..96]);
         ftp_string.copy_from_slice(&bytes[699..727]);
 
-        let byte_ordering = if fmt_string == LITTLE_ENDIAN_STRING.as_bytes() {
-            ByteOrder::LittleEndian
-        } else if fmt_string == BIG_ENDIAN_STRING.as_bytes() {
-            ByteOrder::BigEndian
-        } else {
-            return Err("invalid binary format string");
+        let byte_ordering: ByteOrder = match fmt_string.try_into() {
+            Ok(order) => order,
+            Err(_) if looks_like_text_kernel(&id_word, &fmt_string) => {
+                let text = String::from_utf8_lossy(&fmt_string).into_owned();
+                return Err(DafError::NotBinaryDaf(text));
+            }
+            Err(e) => return Err(e),
         };
 
         let n_double = byte_ordering.i32_from_bytes(&bytes[8..12]);
         let n_integer = byte_ordering.i32_from_bytes(&bytes[12..16]);
-        let forward = byte_ordering.i32_from_bytes(&bytes[76..80]);
-        let backward = byte_ordering.i32_from_bytes(&bytes[80..84]);
-        let first_free = byte_ordering.i32_from_bytes(&bytes[84..88]);
+        if !valid_nd_ni(n_double, n_integer) {
+            return Err(DafError::InvalidSummaryFormat {
+                n_double,
+                n_integer,
+            });
+        }
+        let forward = RecordNumber(byte_ordering.i32_from_bytes(&bytes[76..80]));
+        let backward = RecordNumber(byte_ordering.i32_from_bytes(&bytes[80..84]));
+        let first_free = WordAddress(byte_ordering.i32_from_bytes(&bytes[84..88]));
 
         return Ok(FileRecord {
             id_word,
@@ -121,10 +318,262 @@ impl TryFrom<&[u8]> for FileRecord {
     }
 }
 
+/// Decode the comment area from the bytes of the comment records
+///
+/// Only the first 1000 bytes of each record hold comment text. Lines are terminated by NUL,
+/// which becomes `\n` here, and the comments end at the first EOT (0x04); anything after it is
+/// unused. An empty comment area (no records) has no comments.
+pub fn parse_comment_area(records: &[u8]) -> Result<String, DafError> {
+    let mut text = Vec::new();
+    for record in records.chunks(RECORD_SIZE) {
+        let chars = &record[..record.len().min(COMMENT_CHARS_PER_RECORD)];
+        if let Some(eot) = chars.iter().position(|&b| b == COMMENT_EOT) {
+            text.extend_from_slice(&chars[..eot]);
+            return Ok(String::from_utf8_lossy(&text).replace(COMMENT_EOL as char, "\n"));
+        }
+        text.extend_from_slice(chars);
+    }
+
+    if records.is_empty() {
+        return Ok(String::new());
+    }
+    return Err(DafError::UnterminatedComments);
+}
+
+/// Extract `KEY = VALUE` assignments from comment text
+///
+/// A line counts as an assignment when the text before its first `=` is a single non-empty word;
+/// other lines (prose, separators, blank lines) are skipped. Keys and values are trimmed.
+pub fn comment_assignments(comments: &str) -> Vec<(String, String)> {
+    return comments
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return None;
+            }
+            return Some((key.to_string(), value.trim().to_string()));
+        })
+        .collect();
+}
+
+/// A single array summary, split into its double and integer components
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    /// The double precision components (ND of them)
+    pub doubles: Vec<f64>,
+
+    /// The integer components (NI of them)
+    pub integers: Vec<i32>,
+}
+
+impl Summary {
+    /// The components as SPK-shaped arrays (ND = 2, NI = 6), if the summary has that shape
+    ///
+    /// CK summaries share this shape.
+    pub fn as_spk(&self) -> Option<([f64; 2], [i32; 6])> {
+        let doubles = self.doubles.as_slice().try_into().ok()?;
+        let integers = self.integers.as_slice().try_into().ok()?;
+        return Some((doubles, integers));
+    }
+}
+
+/// A single array in a DAF, described by its summary
+///
+/// By DAF convention the final two integer components of every summary are the initial and final
+/// word addresses (1-based, inclusive) of the array data. This is independent of what the array
+/// means; SPK, PCK and CK segments are all arrays of this form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DafArray {
+    /// The summary describing the array
+    pub summary: Summary,
+
+    /// The word address of the first element of the array
+    pub start: WordAddress,
+
+    /// The word address of the last element of the array
+    pub end: WordAddress,
+}
+
+impl DafArray {
+    /// Interpret a summary as a DAF array, taking the address range from its last two integers
+    pub fn from_summary(summary: &Summary) -> Result<Self, DafError> {
+        let (start, end) = match summary.integers[..] {
+            [.., start, end] => (start, end),
+            _ => {
+                return Err(DafError::SummaryShapeMismatch {
+                    n_double: summary.doubles.len(),
+                    n_integer: summary.integers.len(),
+                })
+            }
+        };
+        if start < 1 || end < start - 1 {
+            return Err(DafError::InvalidArrayAddresses { start, end });
+        }
+        return Ok(DafArray {
+            summary: summary.clone(),
+            start: WordAddress(start),
+            end: WordAddress(end),
+        });
+    }
+
+    /// Number of double precision elements in the array
+    pub fn len(&self) -> usize {
+        return (self.end.0 - self.start.0 + 1) as usize;
+    }
+
+    /// Whether the array has no elements
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Read the array elements from the bytes of the whole file
+    pub fn doubles(&self, file: &[u8], byte_ordering: ByteOrder) -> Result<Vec<f64>, DafError> {
+        // On 32-bit targets the end of the array may not be addressable at all
+        let start = self.start.byte_offset().and_then(ByteOffset::as_usize);
+        let last = start.and_then(|first| {
+            let last = self.len().checked_mul(WORD_SIZE)?.checked_add(first)?;
+            return Some((first, last));
+        });
+        let (first, last) = match last {
+            Some((first, last)) if last <= file.len() => (first, last),
+            _ => {
+                return Err(DafError::BufferTooShort {
+                    needed: last.map_or(usize::MAX, |(_, last)| last),
+                    actual: file.len(),
+                })
+            }
+        };
+        return Ok(file[first..last]
+            .chunks_exact(WORD_SIZE)
+            .map(|word| byte_ordering.f64_from_bytes(word))
+            .collect());
+    }
+}
+
+/// A summary record: three control items followed by packed array summaries
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryRecord {
+    /// The record number of the next summary record in the file (zero if this is the last)
+    pub next: RecordNumber,
+
+    /// The record number of the previous summary record in the file (zero if this is the first)
+    pub previous: RecordNumber,
+
+    /// The number of summaries stored in this record
+    pub n_summaries: i32,
+
+    /// The array summaries stored in this record
+    pub summaries: Vec<Summary>,
+}
+
+/// Convert a summary record control item, stored as a double, to an integer
+///
+/// The control items are record numbers and counts, so anything other than a non-negative
+/// integral value indicates a corrupt record (or one read at the wrong offset).
+fn control_item(value: f64) -> Result<i32, DafError> {
+    if !(0.0..=i32::MAX as f64).contains(&value) || value.fract() != 0.0 {
+        return Err(DafError::CorruptControl(value));
+    }
+    return Ok(value as i32);
+}
+
+impl SummaryRecord {
+    /// Parse a summary record using the summary layout and byte order from the file record
+    pub fn from_bytes(bytes: &[u8], file_record: &FileRecord) -> Result<Self, DafError> {
+        if bytes.len() < RECORD_SIZE {
+            return Err(DafError::BufferTooShort {
+                needed: RECORD_SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        let (nd, ni) = (file_record.n_double, file_record.n_integer);
+        if !valid_nd_ni(nd, ni) {
+            return Err(DafError::InvalidSummaryFormat {
+                n_double: nd,
+                n_integer: ni,
+            });
+        }
+
+        let order = file_record.byte_ordering;
+        let word = |i: usize| order.f64_from_bytes(&bytes[i * WORD_SIZE..]);
+
+        let next = RecordNumber(control_item(word(0))?);
+        let previous = RecordNumber(control_item(word(1))?);
+        let n_summaries = control_item(word(2))?;
+
+        let max = file_record.summaries_per_record();
+        if n_summaries > max {
+            return Err(DafError::TooManySummaries {
+                nsum: n_summaries,
+                max,
+            });
+        }
+
+        let summary_size = file_record.single_summary_size() as usize;
+        let summaries = (0..n_summaries as usize)
+            .map(|i| {
+                let start = SUMMARY_CONTROL_WORDS + i * summary_size;
+                let doubles = (start..start + nd as usize).map(word).collect();
+                let integer_base = (start + nd as usize) * WORD_SIZE;
+                let integers = (0..ni as usize)
+                    .map(|j| order.i32_from_bytes(&bytes[integer_base + 4 * j..]))
+                    .collect();
+                return Summary { doubles, integers };
+            })
+            .collect();
+
+        return Ok(SummaryRecord {
+            next,
+            previous,
+            n_summaries,
+            summaries,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{FileRecord, DESCRIPTION_LENGTH, FTP_STRING_LENGTH, ID_WORD_LENGTH};
+    use super::{
+        comment_assignments, parse_comment_area, DafArray, FileRecord, Summary, SummaryRecord,
+        DESCRIPTION_LENGTH, FTP_STRING_LENGTH, ID_WORD_LENGTH, RECORD_SIZE,
+    };
+    use crate::address::{RecordNumber, WordAddress};
+    use crate::byteorder::ByteOrder;
+    use crate::error::DafError;
     use rand::prelude::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    /// A little endian SPK file record with the given first free address
+    fn file_record_bytes(first_free: i32) -> Vec<u8> {
+        let mut bytes = vec![0; RECORD_SIZE];
+        bytes[0..8].copy_from_slice(b"DAF/SPK ");
+        bytes[8..12].copy_from_slice(&2i32.to_le_bytes());
+        bytes[12..16].copy_from_slice(&6i32.to_le_bytes());
+        bytes[16..76].fill(b' ');
+        bytes[76..80].copy_from_slice(&4i32.to_le_bytes());
+        bytes[80..84].copy_from_slice(&4i32.to_le_bytes());
+        bytes[84..88].copy_from_slice(&first_free.to_le_bytes());
+        bytes[88..96].copy_from_slice(b"LTL-IEEE");
+        return bytes;
+    }
+
+    /// Convert a record from [`file_record_bytes`] to big endian
+    fn big_endian(bytes: &mut [u8]) {
+        for range in [8..12, 12..16, 76..80, 80..84, 84..88] {
+            bytes[range].reverse();
+        }
+        bytes[88..96].copy_from_slice(b"BIG-IEEE");
+    }
+
+    fn hash_of(fr: &FileRecord) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        fr.hash(&mut hasher);
+        return hasher.finish();
+    }
 
     fn random_file_record(nd: i32, ni: i32, f: i32, b: i32, ff: i32) -> FileRecord {
         let mut id_word = [0; ID_WORD_LENGTH];
@@ -136,15 +585,15 @@ mod tests {
         rand::thread_rng().fill_bytes(&mut ftp_string);
 
         return FileRecord {
-            id_word: id_word,
+            id_word,
             n_double: nd,
             n_integer: ni,
-            description: description,
-            forward: f,
-            backward: b,
-            first_free: ff,
+            description,
+            forward: RecordNumber(f),
+            backward: RecordNumber(b),
+            first_free: WordAddress(ff),
             byte_ordering: crate::byteorder::ByteOrder::LittleEndian,
-            ftp_string: ftp_string,
+            ftp_string,
         };
     }
 
@@ -165,4 +614,619 @@ mod tests {
         let fr = random_file_record(1, 3, 1, 1, 10);
         assert_eq!(41, fr.summaries_per_record());
     }
+
+    #[test]
+    fn file_record_invalid_layout() {
+        for (nd, ni) in [
+            (0, 0),
+            (2, 1),
+            (125, 2),
+            (124, 4),
+            (i32::MAX, i32::MAX),
+            (i32::MIN, -5),
+        ] {
+            let mut bytes = file_record_bytes(1025);
+            bytes[8..12].copy_from_slice(&nd.to_le_bytes());
+            bytes[12..16].copy_from_slice(&ni.to_le_bytes());
+            assert_eq!(
+                Err(DafError::InvalidSummaryFormat {
+                    n_double: nd,
+                    n_integer: ni
+                }),
+                FileRecord::try_from(bytes.as_slice())
+            );
+        }
+
+        // The largest layouts that still fit a summary record
+        for (nd, ni) in [(124i32, 2i32), (0, 250), (1, 248)] {
+            let mut bytes = file_record_bytes(1025);
+            bytes[8..12].copy_from_slice(&nd.to_le_bytes());
+            bytes[12..16].copy_from_slice(&ni.to_le_bytes());
+            let fr = FileRecord::try_from(bytes.as_slice()).unwrap();
+            assert!(fr.summaries_per_record() >= 1);
+        }
+    }
+
+    #[test]
+    fn id_word_str() {
+        let mut fr = random_file_record(2, 6, 1, 1, 10);
+
+        fr.id_word.copy_from_slice(b"DAF/SPK ");
+        assert_eq!("DAF/SPK", fr.id_word_str());
+
+        fr.id_word.copy_from_slice(b"DAF/PCK\0");
+        assert_eq!("DAF/PCK", fr.id_word_str());
+
+        fr.id_word.copy_from_slice(b"NAIF/DAF");
+        assert_eq!("NAIF/DAF", fr.id_word_str());
+    }
+
+    #[test]
+    fn description_str() {
+        let mut fr = random_file_record(2, 6, 1, 1, 10);
+
+        fr.description = [b' '; DESCRIPTION_LENGTH];
+        assert_eq!("", fr.description_str());
+
+        fr.description[..5].copy_from_slice(b"DE440");
+        assert_eq!("DE440", fr.description_str());
+
+        fr.description[7..13].copy_from_slice(b"kernel");
+        assert_eq!("DE440  kernel", fr.description_str());
+
+        fr.description = [0; DESCRIPTION_LENGTH];
+        assert_eq!("", fr.description_str());
+    }
+
+    #[test]
+    fn description_is_printable() {
+        let mut fr = random_file_record(2, 6, 1, 1, 10);
+
+        fr.description = [b' '; DESCRIPTION_LENGTH];
+        fr.description[..20].copy_from_slice(b"JPL DE440 (1550-2650");
+        assert!(fr.description_is_printable());
+
+        fr.description[4] = 0xC9;
+        assert!(!fr.description_is_printable());
+
+        fr.description[4] = b'\t';
+        assert!(!fr.description_is_printable());
+
+        // Bytes after the terminating NUL are not part of the text
+        fr.description[4] = b'D';
+        fr.description[30] = 0;
+        fr.description[31] = 0xFF;
+        assert!(fr.description_is_printable());
+    }
+
+    #[test]
+    fn description_str_stops_at_nul() {
+        let mut fr = random_file_record(2, 6, 1, 1, 10);
+
+        fr.description = [b' '; DESCRIPTION_LENGTH];
+        fr.description[..23].copy_from_slice(b"DE440 \0TOOL/1.2\0rev 7\0\0");
+        assert_eq!("DE440", fr.description_str());
+
+        fr.description[0] = 0;
+        assert_eq!("", fr.description_str());
+    }
+
+    #[test]
+    fn ephemeris_name() {
+        let mut fr = random_file_record(2, 6, 1, 1, 10);
+        let mut name = |description: &[u8]| {
+            fr.description = [b' '; DESCRIPTION_LENGTH];
+            fr.description[..description.len()].copy_from_slice(description);
+            return fr.ephemeris_name();
+        };
+
+        assert_eq!(Some("DE440".into()), name(b"JPL planetary ephemeris DE440"));
+        assert_eq!(Some("DE440".into()), name(b"DE-0440LE-0440"));
+        assert_eq!(Some("DE430".into()), name(b"de430t.bsp"));
+        assert_eq!(Some("INPOP19a".into()), name(b"INPOP19A TDB"));
+        assert_eq!(Some("EPM2021".into()), name(b"IAA RAS EPM2021 ephemeris"));
+
+        assert_eq!(None, name(b"NIO2SPK"));
+        assert_eq!(None, name(b"DEEP SPACE 1 RECONSTRUCTED"));
+        assert_eq!(None, name(b"CODE440 DE44"));
+        assert_eq!(None, name(b""));
+    }
+
+    #[test]
+    fn expected_min_bytes() {
+        let fr = random_file_record(2, 6, 1, 1, 2049);
+        assert_eq!(16, fr.num_records());
+        assert_eq!(16384, fr.expected_min_bytes());
+
+        let fr = random_file_record(2, 6, 1, 1, 1);
+        assert_eq!(0, fr.num_records());
+        assert_eq!(0, fr.expected_min_bytes());
+    }
+
+    #[test]
+    fn expected_min_bytes_partial_record() {
+        // The last word in use is the first word of record 17, which must be present in full
+        let fr = random_file_record(2, 6, 1, 1, 2050);
+        assert_eq!(17, fr.num_records());
+        assert_eq!(17408, fr.expected_min_bytes());
+
+        // The last word in use is the final word of record 17
+        let fr = random_file_record(2, 6, 1, 1, 2177);
+        assert_eq!(17, fr.num_records());
+        assert_eq!(17408, fr.expected_min_bytes());
+    }
+
+    #[test]
+    fn expected_min_bytes_extreme_first_free() {
+        for first_free in [i32::MIN, i32::MIN + 1, -1, 0] {
+            let fr = random_file_record(2, 6, 1, 1, first_free);
+            assert_eq!(0, fr.num_records());
+            assert_eq!(0, fr.expected_min_bytes());
+        }
+
+        // Word i32::MAX - 1 is in record 16777216, the last one a word address can reach
+        let fr = random_file_record(2, 6, 1, 1, i32::MAX);
+        assert_eq!(16777216, fr.num_records());
+        assert_eq!(16777216 * RECORD_SIZE as u64, fr.expected_min_bytes());
+    }
+
+    #[test]
+    fn reserved_and_comment_records() {
+        // File record, three comment records, then the first summary record
+        let fr = random_file_record(2, 6, 5, 5, 10);
+        assert_eq!(3, fr.n_reserved_records());
+        assert_eq!(3, fr.n_comment_records());
+
+        let fr = random_file_record(2, 6, 2, 2, 10);
+        assert_eq!(0, fr.n_reserved_records());
+        assert_eq!(0, fr.n_comment_records());
+
+        let fr = random_file_record(2, 6, i32::MIN, 2, 10);
+        assert_eq!(0, fr.n_reserved_records());
+        let fr = random_file_record(2, 6, i32::MAX, 2, 10);
+        assert_eq!(i32::MAX - 2, fr.n_reserved_records());
+    }
+
+    #[test]
+    fn comment_records_in_file() {
+        // 2500 characters of comments span three records, so the summaries start at record 5
+        let mut text = b"x".repeat(2499);
+        text.push(0);
+        text.push(0x04);
+        let mut file = file_record_bytes(1025);
+        file[76..80].copy_from_slice(&5i32.to_le_bytes());
+        file.extend(comment_records(&text));
+
+        let fr = FileRecord::try_from(file.as_slice()).unwrap();
+        assert_eq!(3, fr.n_comment_records());
+
+        let end = (1 + fr.n_comment_records() as usize) * RECORD_SIZE;
+        let comments = parse_comment_area(&file[RECORD_SIZE..end]).unwrap();
+        assert_eq!(format!("{}\n", "x".repeat(2499)), comments);
+
+        // One record short of the count, the comments are cut off before their end marker
+        assert_eq!(
+            Err(DafError::UnterminatedComments),
+            parse_comment_area(&file[RECORD_SIZE..end - RECORD_SIZE])
+        );
+    }
+
+    #[test]
+    fn parsed_records_eq_and_hash() {
+        let a = FileRecord::try_from(file_record_bytes(1025).as_slice()).unwrap();
+        let b = FileRecord::try_from(file_record_bytes(1025).as_slice()).unwrap();
+        let c = FileRecord::try_from(file_record_bytes(2049).as_slice()).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_ne!(a, c);
+    }
+
+    /// A little endian summary record with the given control items and SPK-shaped summaries
+    fn summary_record_bytes(control: [f64; 3], summaries: &[([f64; 2], [i32; 6])]) -> Vec<u8> {
+        let mut bytes = vec![0; RECORD_SIZE];
+        for (i, c) in control.iter().enumerate() {
+            bytes[8 * i..8 * i + 8].copy_from_slice(&c.to_le_bytes());
+        }
+        for (i, (doubles, integers)) in summaries.iter().enumerate() {
+            let base = 8 * (3 + 5 * i);
+            for (j, d) in doubles.iter().enumerate() {
+                bytes[base + 8 * j..base + 8 * j + 8].copy_from_slice(&d.to_le_bytes());
+            }
+            for (j, n) in integers.iter().enumerate() {
+                bytes[base + 16 + 4 * j..base + 20 + 4 * j].copy_from_slice(&n.to_le_bytes());
+            }
+        }
+        return bytes;
+    }
+
+    #[test]
+    fn summary_record_control() {
+        let fr = random_file_record(2, 6, 4, 4, 10);
+        let summaries = [
+            ([-1.0e9, 1.0e9], [399, 3, 1, 2, 1025, 2048]),
+            ([-2.0e9, 2.0e9], [301, 3, 1, 2, 2049, 4096]),
+            ([0.0, 5.0e8], [3, 0, 1, 3, 4097, 8192]),
+        ];
+        let bytes = summary_record_bytes([2.0, 0.0, 3.0], &summaries);
+        let record = SummaryRecord::from_bytes(&bytes, &fr).unwrap();
+
+        assert_eq!(RecordNumber(2), record.next);
+        assert_eq!(RecordNumber(0), record.previous);
+        assert_eq!(3, record.n_summaries);
+        assert_eq!(3, record.summaries.len());
+        for (summary, (doubles, integers)) in record.summaries.iter().zip(summaries.iter()) {
+            assert_eq!(doubles.as_slice(), summary.doubles.as_slice());
+            assert_eq!(integers.as_slice(), summary.integers.as_slice());
+        }
+    }
+
+    #[test]
+    fn summary_record_corrupt_control() {
+        let fr = random_file_record(2, 6, 4, 4, 10);
+
+        let bytes = summary_record_bytes([2.5, 0.0, 0.0], &[]);
+        assert_eq!(
+            Err(DafError::CorruptControl(2.5)),
+            SummaryRecord::from_bytes(&bytes, &fr)
+        );
+
+        let bytes = summary_record_bytes([0.0, -1.0, 0.0], &[]);
+        assert_eq!(
+            Err(DafError::CorruptControl(-1.0)),
+            SummaryRecord::from_bytes(&bytes, &fr)
+        );
+
+        let bytes = summary_record_bytes([0.0, 0.0, f64::NAN], &[]);
+        assert!(SummaryRecord::from_bytes(&bytes, &fr).is_err());
+    }
+
+    #[test]
+    fn summary_record_too_many_summaries() {
+        // 25 SPK summaries fit in a record; claim one more than that
+        let fr = random_file_record(2, 6, 4, 4, 10);
+        let bytes = summary_record_bytes([0.0, 0.0, 26.0], &[]);
+        assert_eq!(
+            Err(DafError::TooManySummaries { nsum: 26, max: 25 }),
+            SummaryRecord::from_bytes(&bytes, &fr)
+        );
+
+        let bytes = summary_record_bytes([0.0, 0.0, 25.0], &[]);
+        assert_eq!(
+            25,
+            SummaryRecord::from_bytes(&bytes, &fr)
+                .unwrap()
+                .summaries
+                .len()
+        );
+    }
+
+    #[test]
+    fn summary_record_too_short() {
+        let fr = random_file_record(2, 6, 4, 4, 10);
+        assert_eq!(
+            Err(DafError::BufferTooShort {
+                needed: RECORD_SIZE,
+                actual: 100
+            }),
+            SummaryRecord::from_bytes(&[0; 100], &fr)
+        );
+    }
+
+    #[test]
+    fn summary_record_invalid_layout() {
+        let bytes = summary_record_bytes([0.0, 0.0, 0.0], &[]);
+        for (nd, ni) in [
+            (0, 0),
+            (125, 2),
+            (2, 251),
+            (i32::MAX, i32::MAX),
+            (i32::MIN, 6),
+        ] {
+            let fr = random_file_record(nd, ni, 4, 4, 10);
+            assert_eq!(
+                Err(DafError::InvalidSummaryFormat {
+                    n_double: nd,
+                    n_integer: ni
+                }),
+                SummaryRecord::from_bytes(&bytes, &fr)
+            );
+        }
+    }
+
+    #[test]
+    fn daf_array_len() {
+        let summary = Summary {
+            doubles: vec![-1.0e9, 1.0e9],
+            integers: vec![399, 3, 1, 2, 1025, 2048],
+        };
+        let array = DafArray::from_summary(&summary).unwrap();
+        assert_eq!(WordAddress(1025), array.start);
+        assert_eq!(WordAddress(2048), array.end);
+        assert_eq!(1024, array.len());
+        assert!(!array.is_empty());
+
+        let summary = Summary {
+            doubles: vec![],
+            integers: vec![7, 7],
+        };
+        assert_eq!(1, DafArray::from_summary(&summary).unwrap().len());
+    }
+
+    #[test]
+    fn daf_array_invalid_addresses() {
+        let summary = Summary {
+            doubles: vec![],
+            integers: vec![2048, 1025],
+        };
+        assert_eq!(
+            Err(DafError::InvalidArrayAddresses {
+                start: 2048,
+                end: 1025
+            }),
+            DafArray::from_summary(&summary)
+        );
+
+        let summary = Summary {
+            doubles: vec![],
+            integers: vec![0, 10],
+        };
+        assert!(DafArray::from_summary(&summary).is_err());
+
+        // Too few integers to hold an address range at all
+        let summary = Summary {
+            doubles: vec![1.0],
+            integers: vec![1025],
+        };
+        assert_eq!(
+            Err(DafError::SummaryShapeMismatch {
+                n_double: 1,
+                n_integer: 1
+            }),
+            DafArray::from_summary(&summary)
+        );
+    }
+
+    #[test]
+    fn daf_array_doubles() {
+        // Second record holds 0.0, 1.0, 2.0, ...
+        let mut file = vec![0; 2 * RECORD_SIZE];
+        for (i, word) in file[RECORD_SIZE..].chunks_exact_mut(8).enumerate() {
+            word.copy_from_slice(&(i as f64).to_le_bytes());
+        }
+        let summary = Summary {
+            doubles: vec![],
+            integers: vec![131, 133],
+        };
+        let array = DafArray::from_summary(&summary).unwrap();
+        assert_eq!(
+            vec![2.0, 3.0, 4.0],
+            array.doubles(&file, ByteOrder::LittleEndian).unwrap()
+        );
+        assert!(array
+            .doubles(&file[..1040], ByteOrder::LittleEndian)
+            .is_err());
+
+        let summary = Summary {
+            doubles: vec![],
+            integers: vec![1, 0],
+        };
+        let array = DafArray::from_summary(&summary).unwrap();
+        assert!(array.is_empty());
+        assert_eq!(Ok(vec![]), array.doubles(&file, ByteOrder::LittleEndian));
+    }
+
+    #[test]
+    fn daf_array_doubles_far_past_end() {
+        // The whole array lies beyond the end of a short file, at the top of the address range
+        let summary = Summary {
+            doubles: vec![],
+            integers: vec![i32::MAX - 1, i32::MAX],
+        };
+        let array = DafArray::from_summary(&summary).unwrap();
+        let result = array.doubles(&[0; RECORD_SIZE], ByteOrder::LittleEndian);
+        assert!(matches!(
+            result,
+            Err(DafError::BufferTooShort {
+                actual: RECORD_SIZE,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn file_record_format_string() {
+        let mut bytes = file_record_bytes(1025);
+        let fr = FileRecord::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(ByteOrder::LittleEndian, fr.byte_ordering);
+
+        big_endian(&mut bytes);
+        let fr = FileRecord::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(ByteOrder::BigEndian, fr.byte_ordering);
+        assert_eq!((2, 6), (fr.n_double, fr.n_integer));
+
+        bytes[88..96].copy_from_slice(&[0xff, 0x00, 0x13, 0x80, 0x7f, 0x01, 0x02, 0x03]);
+        assert!(matches!(
+            FileRecord::try_from(bytes.as_slice()),
+            Err(DafError::InvalidFormatString(_))
+        ));
+    }
+
+    #[test]
+    fn file_record_requires_byte_swap() {
+        let mut bytes = file_record_bytes(1025);
+        let fr = FileRecord::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(cfg!(target_endian = "big"), fr.requires_byte_swap());
+
+        big_endian(&mut bytes);
+        let fr = FileRecord::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(cfg!(target_endian = "little"), fr.requires_byte_swap());
+    }
+
+    #[test]
+    fn file_record_text_kernel() {
+        // The start of a leapseconds kernel, padded out to a full record
+        let mut bytes =
+            b"KPL/LSK\n\nLEAPSECONDS KERNEL FILE\n=========================\n\n".repeat(20);
+        bytes.truncate(RECORD_SIZE);
+        assert!(matches!(
+            FileRecord::try_from(bytes.as_slice()),
+            Err(DafError::NotBinaryDaf(_))
+        ));
+    }
+
+    #[test]
+    fn file_record_unsupported_format() {
+        // A DAF with a VAX or missing format string is a real DAF that this crate cannot read
+        let mut bytes = file_record_bytes(1025);
+        for fmt_string in [b"VAX-GFLT", b"VAX-DFLT", b"        "] {
+            bytes[88..96].copy_from_slice(fmt_string);
+            assert_eq!(
+                Err(DafError::InvalidFormatString(*fmt_string)),
+                FileRecord::try_from(bytes.as_slice())
+            );
+        }
+
+        bytes[0..8].copy_from_slice(b"NAIF/DAF");
+        assert_eq!(
+            Err(DafError::InvalidFormatString(*b"        ")),
+            FileRecord::try_from(bytes.as_slice())
+        );
+    }
+
+    #[test]
+    fn file_record_text_kernel_id_word() {
+        // A frames kernel whose format region happens to hold something other than text
+        let mut bytes = file_record_bytes(1025);
+        bytes[0..8].copy_from_slice(b"KPL/FK\n\n");
+        bytes[88..96].copy_from_slice(&[0xff; 8]);
+        assert!(matches!(
+            FileRecord::try_from(bytes.as_slice()),
+            Err(DafError::NotBinaryDaf(_))
+        ));
+
+        // Printable, but not a DAF id word
+        bytes[0..8].copy_from_slice(b"\\beginda");
+        bytes[88..96].copy_from_slice(b"ta\n     ");
+        assert!(matches!(
+            FileRecord::try_from(bytes.as_slice()),
+            Err(DafError::NotBinaryDaf(_))
+        ));
+    }
+
+    #[test]
+    fn file_record_from_array() {
+        let bytes = file_record_bytes(1025);
+        let array: [u8; RECORD_SIZE] = bytes.as_slice().try_into().unwrap();
+        assert_eq!(
+            FileRecord::try_from(bytes.as_slice()),
+            FileRecord::try_from(&array)
+        );
+        assert!(FileRecord::try_from(&array).is_ok());
+    }
+
+    #[test]
+    fn file_record_ftp_bytes() {
+        let mut bytes = file_record_bytes(1025);
+        let ftp: Vec<u8> = (1..=28).collect();
+        bytes[699..727].copy_from_slice(&ftp);
+        bytes[698] = 0xff;
+        bytes[727] = 0xff;
+        let fr = FileRecord::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(ftp.as_slice(), fr.ftp_bytes());
+    }
+
+    #[test]
+    fn file_record_too_short() {
+        assert_eq!(
+            Err(DafError::BufferTooShort {
+                needed: RECORD_SIZE,
+                actual: 96
+            }),
+            FileRecord::try_from(&file_record_bytes(1025)[..96])
+        );
+    }
+
+    #[test]
+    fn summary_as_spk() {
+        let summary = Summary {
+            doubles: vec![-1.0e9, 1.0e9],
+            integers: vec![399, 3, 1, 2, 1025, 2048],
+        };
+        assert_eq!(
+            Some(([-1.0e9, 1.0e9], [399, 3, 1, 2, 1025, 2048])),
+            summary.as_spk()
+        );
+
+        // A PCK-shaped summary (ND = 2, NI = 5)
+        let summary = Summary {
+            doubles: vec![-1.0e9, 1.0e9],
+            integers: vec![31006, 1, 2, 1025, 2048],
+        };
+        assert_eq!(None, summary.as_spk());
+
+        let summary = Summary {
+            doubles: vec![-1.0e9],
+            integers: vec![399, 3, 1, 2, 1025, 2048],
+        };
+        assert_eq!(None, summary.as_spk());
+    }
+
+    /// Comment records holding the given comment text, with junk after the characters in use
+    fn comment_records(text: &[u8]) -> Vec<u8> {
+        let n_records = text.len().div_ceil(1000).max(1);
+        let mut bytes = vec![b'#'; n_records * RECORD_SIZE];
+        for (record, chunk) in bytes.chunks_mut(RECORD_SIZE).zip(text.chunks(1000)) {
+            record[..chunk.len()].copy_from_slice(chunk);
+        }
+        return bytes;
+    }
+
+    #[test]
+    fn comment_area() {
+        let text = b"JPL planetary ephemeris\0\0Coverage: 1550 to 2650\0\x04";
+        let comments = parse_comment_area(&comment_records(text)).unwrap();
+        assert_eq!(
+            "JPL planetary ephemeris\n\nCoverage: 1550 to 2650\n",
+            comments
+        );
+    }
+
+    #[test]
+    fn comment_area_multiple_records() {
+        // Lines of 50 characters spanning three records
+        let mut text = Vec::new();
+        for i in 0..50 {
+            text.extend_from_slice(format!("{i:049}").as_bytes());
+            text.push(0);
+        }
+        text.push(4);
+        let comments = parse_comment_area(&comment_records(&text)).unwrap();
+        let lines: Vec<&str> = comments.lines().collect();
+        assert_eq!(50, lines.len());
+        assert_eq!(format!("{:049}", 49), lines[49]);
+        assert!(comments.ends_with('\n'));
+    }
+
+    #[test]
+    fn comment_area_unterminated() {
+        assert_eq!(Ok(String::new()), parse_comment_area(&[]));
+        assert_eq!(
+            Err(DafError::UnterminatedComments),
+            parse_comment_area(&comment_records(b"no end marker\0"))
+        );
+    }
+
+    #[test]
+    fn comment_area_assignments() {
+        let text = b"Planetary ephemeris DE440\0\0PRODUCER_ID = JPL\0  START_TIME=1549 DEC 31\0\
+            Time range = long enough\0=====\0\x04";
+        let comments = parse_comment_area(&comment_records(text)).unwrap();
+        assert_eq!(
+            vec![
+                ("PRODUCER_ID".to_string(), "JPL".to_string()),
+                ("START_TIME".to_string(), "1549 DEC 31".to_string()),
+            ],
+            comment_assignments(&comments)
+        );
+    }
 }
@@ -0,0 +1,106 @@
+//! Errors raised while reading Double Precision Array Files
+
+use std::fmt;
+
+/// An error encountered while parsing a DAF
+#[derive(Debug, Clone, PartialEq)]
+pub enum DafError {
+    /// The byte buffer is too short for the structure being parsed
+    BufferTooShort { needed: usize, actual: usize },
+
+    /// The binary format string is neither `LTL-IEEE` nor `BIG-IEEE`
+    InvalidFormatString([u8; 8]),
+
+    /// The file record has no recognized format string and does not start with a DAF id word
+    ///
+    /// This usually means a text kernel (e.g. an LSK or FK) was passed to the DAF parser.
+    NotBinaryDaf(String),
+
+    /// The file record's ND/NI values do not describe a valid summary layout
+    InvalidSummaryFormat { n_double: i32, n_integer: i32 },
+
+    /// A summary record control item is not a non-negative integer
+    CorruptControl(f64),
+
+    /// A summary record claims more summaries than fit in a record
+    TooManySummaries { nsum: i32, max: i32 },
+
+    /// The comment area has no end-of-comments (EOT) marker
+    UnterminatedComments,
+
+    /// A summary does not have the number of components its interpretation requires
+    SummaryShapeMismatch { n_double: usize, n_integer: usize },
+
+    /// An SPK segment data type outside of the documented range
+    ImplausibleSpkType(i32),
+
+    /// A CK segment data type outside of the documented range
+    ImplausibleCkType(i32),
+
+    /// A segment coverage window whose start is after its stop
+    InvertedEpochWindow { start: f64, stop: f64 },
+
+    /// An array summary does not hold a valid initial/final address pair
+    InvalidArrayAddresses { start: i32, end: i32 },
+}
+
+impl fmt::Display for DafError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            DafError::BufferTooShort { needed, actual } => {
+                write!(
+                    f,
+                    "byte buffer too short: need {needed} bytes, got {actual}"
+                )
+            }
+            DafError::InvalidFormatString(bytes) => {
+                write!(f, "invalid binary format string: {bytes:02x?}")
+            }
+            DafError::NotBinaryDaf(text) => write!(
+                f,
+                "not a binary DAF (format string {text:?}); is this a text kernel?"
+            ),
+            DafError::InvalidSummaryFormat {
+                n_double,
+                n_integer,
+            } => write!(
+                f,
+                "invalid summary format: ND = {n_double}, NI = {n_integer}"
+            ),
+            DafError::CorruptControl(value) => {
+                write!(f, "corrupt summary record control item: {value}")
+            }
+            DafError::TooManySummaries { nsum, max } => write!(
+                f,
+                "summary record claims {nsum} summaries but holds at most {max}"
+            ),
+            DafError::UnterminatedComments => {
+                write!(f, "comment area has no end-of-comments marker")
+            }
+            DafError::SummaryShapeMismatch {
+                n_double,
+                n_integer,
+            } => write!(
+                f,
+                "unexpected summary shape: {n_double} doubles and {n_integer} integers"
+            ),
+            DafError::ImplausibleSpkType(data_type) => {
+                write!(f, "implausible SPK data type: {data_type}")
+            }
+            DafError::ImplausibleCkType(data_type) => {
+                write!(f, "implausible CK data type: {data_type}")
+            }
+            DafError::InvertedEpochWindow { start, stop } => {
+                write!(
+                    f,
+                    "segment window starts at {start} after it stops at {stop}"
+                )
+            }
+            DafError::InvalidArrayAddresses { start, end } => {
+                write!(f, "invalid array address range: {start} to {end}")
+            }
+        };
+    }
+}
+
+impl std::error::Error for DafError {}
@@ -0,0 +1,198 @@
+//! Fixed inertial reference frames and the constant rotations between them
+
+/// Radians per arcsecond
+const ARCSECOND: f64 = std::f64::consts::PI / 180.0 / 3600.0;
+
+/// Obliquity of the ecliptic at J2000 (radians), 84381.448 arcseconds
+const J2000_OBLIQUITY: f64 = 84381.448 * ARCSECOND;
+
+/// IAU 1976 precession angles (zeta, theta, z) from J2000 back to B1950 (radians)
+const B1950_PRECESSION: (f64, f64, f64) = (
+    1153.04066200330 * ARCSECOND,
+    -1002.26108439117 * ARCSECOND,
+    1152.84248596724 * ARCSECOND,
+);
+
+/// 3x3 rotation matrix, row-major
+type Matrix3 = [[f64; 3]; 3];
+
+/// An inertial reference frame related to J2000 by a constant rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frame {
+    /// Earth mean equator and dynamical equinox of J2000
+    J2000,
+
+    /// Mean ecliptic and equinox of J2000
+    EclipJ2000,
+
+    /// Earth mean equator and dynamical equinox of B1950, related to J2000 by precession
+    B1950,
+}
+
+impl Frame {
+    /// The frame with the given NAIF frame ID, if it is one of the fixed inertial frames here
+    pub fn from_id(id: i32) -> Option<Frame> {
+        return match id {
+            1 => Some(Frame::J2000),
+            2 => Some(Frame::B1950),
+            17 => Some(Frame::EclipJ2000),
+            _ => None,
+        };
+    }
+
+    /// The NAIF frame ID, as stored in SPK segment descriptors
+    pub fn id(&self) -> i32 {
+        return match self {
+            Frame::J2000 => 1,
+            Frame::B1950 => 2,
+            Frame::EclipJ2000 => 17,
+        };
+    }
+
+    /// The NAIF frame name
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Frame::J2000 => "J2000",
+            Frame::B1950 => "B1950",
+            Frame::EclipJ2000 => "ECLIPJ2000",
+        };
+    }
+
+    /// Rotation taking a vector expressed in J2000 into this frame
+    pub fn rotation_from_j2000(&self) -> Matrix3 {
+        return match self {
+            Frame::J2000 => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            Frame::EclipJ2000 => {
+                let (s, c) = J2000_OBLIQUITY.sin_cos();
+                [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+            }
+            Frame::B1950 => {
+                let (zeta, theta, z) = B1950_PRECESSION;
+                multiply(&multiply(&rotate_z(z), &rotate_y(theta)), &rotate_z(zeta))
+            }
+        };
+    }
+
+    /// Rotation taking a vector expressed in this frame into `other`
+    pub fn rotation_to(&self, other: Frame) -> Matrix3 {
+        let from = self.rotation_from_j2000();
+        let to = other.rotation_from_j2000();
+
+        // The inverse of a rotation is its transpose, so this is `to * transpose(from)`
+        let mut m = [[0.0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| to[i][k] * from[j][k]).sum();
+            }
+        }
+        return m;
+    }
+
+    /// Express a state (position and velocity) given in this frame in `other`
+    ///
+    /// The frames are inertial and related by a constant rotation, so position and velocity
+    /// rotate identically.
+    pub fn transform_state(&self, other: Frame, state: &[f64; 6]) -> [f64; 6] {
+        let m = self.rotation_to(other);
+        let mut out = [0.0; 6];
+        for i in 0..3 {
+            out[i] = (0..3).map(|k| m[i][k] * state[k]).sum();
+            out[i + 3] = (0..3).map(|k| m[i][k] * state[k + 3]).sum();
+        }
+        return out;
+    }
+}
+
+/// Frame rotation by `angle` about the z axis
+fn rotate_z(angle: f64) -> Matrix3 {
+    let (s, c) = angle.sin_cos();
+    return [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]];
+}
+
+/// Frame rotation by `angle` about the y axis
+fn rotate_y(angle: f64) -> Matrix3 {
+    let (s, c) = angle.sin_cos();
+    return [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]];
+}
+
+/// Matrix product `a * b`
+fn multiply(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut m = [[0.0; 3]; 3];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    return m;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Frame;
+
+    const STATE: [f64; 6] = [1.0e8, -2.0e7, 3.0e6, 10.0, 20.0, -30.0];
+
+    #[test]
+    fn ids_and_names() {
+        assert_eq!(Some("J2000"), Frame::from_id(1).map(|f| f.name()));
+        assert_eq!(Some("B1950"), Frame::from_id(2).map(|f| f.name()));
+        assert_eq!(Some("ECLIPJ2000"), Frame::from_id(17).map(|f| f.name()));
+        assert_eq!(None, Frame::from_id(0));
+        assert_eq!(None, Frame::from_id(10013));
+
+        for frame in [Frame::J2000, Frame::EclipJ2000, Frame::B1950] {
+            assert_eq!(Some(frame), Frame::from_id(frame.id()));
+        }
+    }
+
+    #[test]
+    fn native_frame_is_identity() {
+        assert_eq!(STATE, Frame::J2000.transform_state(Frame::J2000, &STATE));
+        assert_eq!(
+            STATE,
+            Frame::EclipJ2000.transform_state(Frame::EclipJ2000, &STATE)
+        );
+    }
+
+    #[test]
+    fn ecliptic_obliquity() {
+        // The J2000 pole expressed in the ecliptic frame is tilted by the obliquity
+        let pole = [0.0, 0.0, 1.0, 0.0, 0.0, 0.0];
+        let out = Frame::J2000.transform_state(Frame::EclipJ2000, &pole);
+        let tilt = out[2].acos().to_degrees();
+        assert!((tilt - 23.439).abs() < 1e-3);
+        assert_eq!(0.0, out[0]);
+        assert!(out[1] > 0.0);
+    }
+
+    #[test]
+    fn round_trip() {
+        let ecl = Frame::J2000.transform_state(Frame::EclipJ2000, &STATE);
+        let back = Frame::EclipJ2000.transform_state(Frame::J2000, &ecl);
+        for i in 0..6 {
+            assert!((back[i] - STATE[i]).abs() <= 1e-12 * STATE[i].abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn b1950_precession() {
+        // IAU 1976 precession matrix from J2000 to B1950
+        let expected = [
+            [0.9999257080, 0.0111789381, 0.0048590038],
+            [-0.0111789381, 0.9999375133, -0.0000271579],
+            [-0.0048590038, -0.0000271626, 0.9999881946],
+        ];
+        let m = Frame::J2000.rotation_to(Frame::B1950);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((m[i][j] - expected[i][j]).abs() < 1e-9);
+            }
+        }
+
+        let b1950 = Frame::J2000.transform_state(Frame::B1950, &STATE);
+        let back = Frame::B1950.transform_state(Frame::J2000, &b1950);
+        for i in 0..6 {
+            assert!((back[i] - STATE[i]).abs() <= 1e-12 * STATE[i].abs().max(1.0));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Hermite interpolation from values and first derivatives at a set of nodes
+//!
+//! This is the interpolation used by SPK Type 13, where each position component is interpolated
+//! from position and velocity samples. Besides the value and first derivative (velocity) it also
+//! gives the second derivative (acceleration) of the same interpolating polynomial.
+
+/// Value, first and second derivative at `t` of the Hermite polynomial through the nodes
+///
+/// The polynomial of degree `2 * epochs.len() - 1` matches `values` and `derivatives` at each of
+/// the `epochs`, which must be distinct. `None` if there are no epochs, or the three slices differ
+/// in length.
+pub fn interpolate(
+    epochs: &[f64],
+    values: &[f64],
+    derivatives: &[f64],
+    t: f64,
+) -> Option<(f64, f64, f64)> {
+    if epochs.is_empty() || epochs.len() != values.len() || epochs.len() != derivatives.len() {
+        return None;
+    }
+
+    // Newton divided differences over the nodes with each epoch repeated twice. Repeated nodes
+    // take the derivative as their first divided difference.
+    let n = 2 * epochs.len();
+    let z: Vec<f64> = (0..n).map(|i| epochs[i / 2]).collect();
+    let mut column: Vec<f64> = (0..n).map(|i| values[i / 2]).collect();
+    let mut coeffs = vec![column[0]];
+    for order in 1..n {
+        for i in (order..n).rev() {
+            column[i] = if order == 1 && i % 2 == 1 {
+                derivatives[i / 2]
+            } else {
+                (column[i] - column[i - 1]) / (z[i] - z[i - order])
+            };
+        }
+        coeffs.push(column[order]);
+    }
+
+    // Horner's scheme on the Newton form, carrying the first two derivatives along
+    let (mut p, mut dp, mut ddp) = (coeffs[n - 1], 0.0, 0.0);
+    for k in (0..n - 1).rev() {
+        let dx = t - z[k];
+        ddp = ddp * dx + 2.0 * dp;
+        dp = dp * dx + p;
+        p = p * dx + coeffs[k];
+    }
+    return Some((p, dp, ddp));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interpolate;
+
+    /// A cubic, with its first and second derivatives
+    fn cubic(t: f64) -> (f64, f64, f64) {
+        let value = 2.0 * t * t * t - t * t + 3.0 * t + 1.0;
+        let derivative = 6.0 * t * t - 2.0 * t + 3.0;
+        return (value, derivative, 12.0 * t - 2.0);
+    }
+
+    fn samples(epochs: &[f64], f: impl Fn(f64) -> (f64, f64, f64)) -> (Vec<f64>, Vec<f64>) {
+        return epochs.iter().map(|&t| (f(t).0, f(t).1)).unzip();
+    }
+
+    #[test]
+    fn matches_nodes() {
+        let epochs = [0.0, 1.5, 2.0, 4.0];
+        let (values, derivatives) = samples(&epochs, |t| (t.sin(), t.cos(), -t.sin()));
+        for (i, &t) in epochs.iter().enumerate() {
+            let (p, dp, _) = interpolate(&epochs, &values, &derivatives, t).unwrap();
+            assert!((p - values[i]).abs() < 1e-14);
+            assert!((dp - derivatives[i]).abs() < 1e-13);
+        }
+    }
+
+    #[test]
+    fn reproduces_cubic() {
+        // Two nodes already determine a cubic, so more nodes must reproduce it exactly
+        let epochs = [-1.0, 0.5, 2.0];
+        let (values, derivatives) = samples(&epochs, cubic);
+        for &t in &[-1.0, -0.3, 0.0, 1.1, 2.0, 2.5] {
+            let (p, dp, ddp) = interpolate(&epochs, &values, &derivatives, t).unwrap();
+            let (value, derivative, second) = cubic(t);
+            assert!((p - value).abs() < 1e-12);
+            assert!((dp - derivative).abs() < 1e-12);
+            assert!((ddp - second).abs() < 1e-11);
+        }
+    }
+
+    #[test]
+    fn acceleration_matches_finite_difference() {
+        let epochs = [0.0, 1.0, 2.0, 3.0];
+        let (values, derivatives) = samples(&epochs, cubic);
+        let h = 1e-6;
+        for &t in &[0.2, 1.0, 1.7, 2.9] {
+            let (_, ahead, _) = interpolate(&epochs, &values, &derivatives, t + h).unwrap();
+            let (_, behind, _) = interpolate(&epochs, &values, &derivatives, t - h).unwrap();
+            let (_, _, ddp) = interpolate(&epochs, &values, &derivatives, t).unwrap();
+            assert!((ddp - (ahead - behind) / (2.0 * h)).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn single_node() {
+        // One node gives the tangent line
+        let (p, dp, ddp) = interpolate(&[2.0], &[5.0], &[3.0], 4.0).unwrap();
+        assert_eq!((11.0, 3.0, 0.0), (p, dp, ddp));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(None, interpolate(&[], &[], &[], 0.0));
+        assert_eq!(None, interpolate(&[0.0, 1.0], &[1.0], &[1.0, 2.0], 0.5));
+        assert_eq!(None, interpolate(&[0.0, 1.0], &[1.0, 2.0], &[1.0], 0.5));
+        assert_eq!(None, interpolate(&[0.0], &[1.0, 2.0], &[1.0, 2.0], 0.5));
+    }
+}
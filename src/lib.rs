@@ -0,0 +1,19 @@
+//! A SPICE utility written in rust
+
+pub mod address;
+pub mod byteorder;
+pub mod cheby;
+pub mod ck;
+pub mod daf;
+pub mod error;
+pub mod frames;
+pub mod hermite;
+#[cfg(feature = "npy")]
+pub mod npy;
+pub mod spk;
+pub mod state;
+pub mod time;
+pub mod twobody;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,18 +1,27 @@
 use std::fs::File;
 use std::io::{BufReader, Read};
 
-use crate::daf::FileRecord;
-
-mod byteorder;
-mod daf;
+use melange::daf::{FileRecord, RECORD_SIZE};
 
 fn main() {
     let mut input = BufReader::new(File::open("de440.bsp").expect("Could not open"));
-    let mut buffer = vec![0; 1024];
+    let mut buffer = vec![0; RECORD_SIZE];
 
     input.read_exact(buffer.as_mut_slice()).expect("shit, man");
 
     let fr = FileRecord::try_from(buffer.as_slice()).expect("failed try from");
 
-    println!("{:?}", String::from_utf8_lossy(&fr.description));
+    let description = fr.description_str();
+    if description.is_empty() {
+        println!("(no description)");
+    } else if fr.description_is_printable() {
+        println!("{:?}", description);
+    } else {
+        let hex: Vec<String> = fr.description.iter().map(|b| format!("{b:02x}")).collect();
+        println!("{}", hex.join(" "));
+    }
+
+    if let Some(name) = fr.ephemeris_name() {
+        println!("ephemeris: {name}");
+    }
 }
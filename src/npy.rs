@@ -0,0 +1,95 @@
+//! Writing tables of doubles in the NumPy `.npy` format
+//!
+//! Only what ephemeris output needs is supported: a two dimensional, C ordered, little endian
+//! `float64` array, written with version 1.0 of the format.
+
+use std::io::Write;
+
+/// Magic string at the start of every `.npy` file
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Major and minor format version
+const VERSION: [u8; 2] = [1, 0];
+
+/// The magic string, version and header length together are padded to a multiple of this
+const HEADER_ALIGNMENT: usize = 64;
+
+/// Write `rows` as an `(rows.len(), N)` float64 array in `.npy` format
+///
+/// For states this is typically `N = 7` with columns `et, x, y, z, vx, vy, vz`, which NumPy reads
+/// back with `numpy.load`.
+pub fn write_npy<W: Write, const N: usize>(
+    writer: &mut W,
+    rows: &[[f64; N]],
+) -> std::io::Result<()> {
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+        rows.len(),
+        N
+    );
+
+    // The header ends with a newline and is space padded so that the data is aligned
+    let preamble = MAGIC.len() + VERSION.len() + std::mem::size_of::<u16>();
+    let unpadded = preamble + header.len() + 1;
+    let padding = (HEADER_ALIGNMENT - unpadded % HEADER_ALIGNMENT) % HEADER_ALIGNMENT;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION)?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for value in rows.iter().flatten() {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_npy, HEADER_ALIGNMENT};
+
+    #[test]
+    fn header() {
+        let rows = [[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0], [7.0; 7], [-1.5; 7]];
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, &rows).unwrap();
+
+        assert_eq!(b"\x93NUMPY\x01\x00", &bytes[..8]);
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        let data_start = 10 + header_len;
+        assert_eq!(0, data_start % HEADER_ALIGNMENT);
+
+        let header = std::str::from_utf8(&bytes[10..data_start]).unwrap();
+        assert!(header.ends_with('\n'));
+        assert_eq!(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (3, 7), }",
+            header.trim_end()
+        );
+        assert_eq!(data_start + 3 * 7 * 8, bytes.len());
+    }
+
+    #[test]
+    fn data_is_row_major() {
+        let rows = [[1.0, 2.0], [3.0, 4.0]];
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, &rows).unwrap();
+
+        let data_start = bytes.len() - 4 * 8;
+        let values: Vec<f64> = bytes[data_start..]
+            .chunks_exact(8)
+            .map(|word| f64::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0], values);
+    }
+
+    #[test]
+    fn empty() {
+        let mut bytes = Vec::new();
+        write_npy::<_, 7>(&mut bytes, &[]).unwrap();
+        assert_eq!(0, bytes.len() % HEADER_ALIGNMENT);
+        assert!(std::str::from_utf8(&bytes[10..])
+            .unwrap()
+            .contains("'shape': (0, 7)"));
+    }
+}
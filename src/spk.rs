@@ -0,0 +1,503 @@
+//! Definition of types for handling Spacecraft and Planet Kernel (SPK) segments
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::address::WordAddress;
+use crate::daf::Summary;
+use crate::error::DafError;
+
+/// The largest SPK data type documented by NAIF
+const MAX_SPK_TYPE: i32 = 21;
+
+/// SPK data types with a published definition
+///
+/// Every one of these yields velocity as well as position, even when only one is stored: Type 2
+/// stores position Chebyshev coefficients and differentiates them for velocity, while Type 20
+/// stores velocity coefficients and integrates them for position. The remaining numbers in
+/// `1..=MAX_SPK_TYPE` are reserved or unassigned.
+const DEFINED_SPK_TYPES: [i32; 16] = [1, 2, 3, 5, 8, 9, 10, 12, 13, 14, 15, 17, 18, 19, 20, 21];
+
+/// Description of a single SPK segment, decoded from its DAF array summary
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpkSegmentDescriptor {
+    /// NAIF ID of the body whose state the segment describes
+    pub target: i32,
+
+    /// NAIF ID of the body the states are relative to
+    pub center: i32,
+
+    /// NAIF ID of the reference frame of the states
+    pub frame: i32,
+
+    /// SPK data type of the segment
+    pub data_type: i32,
+
+    /// Start of the coverage window (TDB seconds past J2000)
+    pub start_et: f64,
+
+    /// End of the coverage window (TDB seconds past J2000)
+    pub stop_et: f64,
+
+    /// Word address of the first element of the segment data
+    pub start_addr: WordAddress,
+
+    /// Word address of the last element of the segment data
+    pub end_addr: WordAddress,
+}
+
+impl SpkSegmentDescriptor {
+    /// Decode an SPK summary (ND = 2, NI = 6)
+    ///
+    /// Data types outside of the documented SPK range almost always mean the summary was read
+    /// with the wrong byte order or offset, so they are rejected here rather than at query time.
+    /// The same goes for a coverage window that ends before it starts. A window that is a single
+    /// instant is allowed.
+    pub fn from_summary(summary: &Summary) -> Result<Self, DafError> {
+        let ([start_et, stop_et], [target, center, frame, data_type, start_addr, end_addr]) =
+            summary.as_spk().ok_or(DafError::SummaryShapeMismatch {
+                n_double: summary.doubles.len(),
+                n_integer: summary.integers.len(),
+            })?;
+
+        if !(1..=MAX_SPK_TYPE).contains(&data_type) {
+            return Err(DafError::ImplausibleSpkType(data_type));
+        }
+        if start_et > stop_et {
+            return Err(DafError::InvertedEpochWindow {
+                start: start_et,
+                stop: stop_et,
+            });
+        }
+
+        return Ok(SpkSegmentDescriptor {
+            target,
+            center,
+            frame,
+            data_type,
+            start_et,
+            stop_et,
+            start_addr: WordAddress(start_addr),
+            end_addr: WordAddress(end_addr),
+        });
+    }
+
+    /// Length of the coverage window (seconds)
+    pub fn duration(&self) -> f64 {
+        return self.stop_et - self.start_et;
+    }
+
+    /// Whether the coverage window, bounds included, holds `et`
+    pub fn contains(&self, et: f64) -> bool {
+        return self.contains_within(et, 0.0);
+    }
+
+    /// Whether the coverage window, widened by `tolerance` seconds at each end, holds `et`
+    ///
+    /// A small tolerance accepts epochs that miss a bound only through rounding, e.g. after a
+    /// UTC to ET conversion.
+    pub fn contains_within(&self, et: f64, tolerance: f64) -> bool {
+        return (self.start_et - tolerance..=self.stop_et + tolerance).contains(&et);
+    }
+
+    /// Whether states evaluated from this segment include velocity
+    ///
+    /// This is true for every defined SPK type, whether the velocity is stored or derived. For
+    /// reserved or unassigned type numbers the output is unknown, so this is false.
+    pub fn provides_velocity(&self) -> bool {
+        return DEFINED_SPK_TYPES.contains(&self.data_type);
+    }
+}
+
+/// The distinct target bodies of the segments
+pub fn bodies(descriptors: &[SpkSegmentDescriptor]) -> BTreeSet<i32> {
+    return descriptors
+        .iter()
+        .map(|descriptor| descriptor.target)
+        .collect();
+}
+
+/// Whether any segment has `body` as its target, regardless of epoch
+pub fn has_body(descriptors: &[SpkSegmentDescriptor], body: i32) -> bool {
+    return descriptors
+        .iter()
+        .any(|descriptor| descriptor.target == body);
+}
+
+/// Coverage overlaps between segments of the same target
+///
+/// Returns each target with the window shared by two of its segments, ordered by target and
+/// then start of overlap. Segments that only meet at a boundary epoch, as consecutive segments
+/// of one kernel usually do, do not overlap. Overlaps are normal when kernels are layered on
+/// purpose, but may also mean the same kernel was loaded twice.
+pub fn detect_overlaps(descriptors: &[SpkSegmentDescriptor]) -> Vec<(i32, (f64, f64))> {
+    let mut sorted: Vec<&SpkSegmentDescriptor> = descriptors.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.target
+            .cmp(&b.target)
+            .then(a.start_et.total_cmp(&b.start_et))
+    });
+
+    let mut overlaps = Vec::new();
+    for (i, first) in sorted.iter().enumerate() {
+        let later = sorted[i + 1..]
+            .iter()
+            .take_while(|second| second.target == first.target && second.start_et < first.stop_et);
+        for second in later {
+            let window = (second.start_et, first.stop_et.min(second.stop_et));
+            overlaps.push((first.target, window));
+        }
+    }
+    overlaps.sort_by(|a, b| a.0.cmp(&b.0).then(a.1 .0.total_cmp(&b.1 .0)));
+    return overlaps;
+}
+
+/// The first segment with the given target, center and data type
+///
+/// This bypasses selection by epoch, for callers that know exactly which segment they want.
+pub fn find_segment(
+    descriptors: &[SpkSegmentDescriptor],
+    target: i32,
+    center: i32,
+    data_type: i32,
+) -> Option<&SpkSegmentDescriptor> {
+    return descriptors.iter().find(|descriptor| {
+        descriptor.target == target
+            && descriptor.center == center
+            && descriptor.data_type == data_type
+    });
+}
+
+/// Targets whose segments are stored in more than one reference frame
+///
+/// Returns each such target with its sorted frame IDs, ordered by target. Segments of one body
+/// in different frames are legal, but when several kernels are loaded together they are usually
+/// a sign of mismatched files.
+pub fn frame_conflicts(descriptors: &[SpkSegmentDescriptor]) -> Vec<(i32, Vec<i32>)> {
+    let mut frames: BTreeMap<i32, BTreeSet<i32>> = BTreeMap::new();
+    for descriptor in descriptors {
+        frames
+            .entry(descriptor.target)
+            .or_default()
+            .insert(descriptor.frame);
+    }
+    return frames
+        .into_iter()
+        .filter(|(_, frames)| frames.len() > 1)
+        .map(|(target, frames)| (target, frames.into_iter().collect()))
+        .collect();
+}
+
+/// The distinct `(target, center)` pairs of the segments, in order
+///
+/// These are the edges of the ephemeris graph: a state of one body relative to another can only
+/// be built by chaining them.
+pub fn relations(descriptors: &[SpkSegmentDescriptor]) -> Vec<(i32, i32)> {
+    let pairs: BTreeSet<(i32, i32)> = descriptors
+        .iter()
+        .map(|descriptor| (descriptor.target, descriptor.center))
+        .collect();
+    return pairs.into_iter().collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bodies, detect_overlaps, find_segment, frame_conflicts, has_body, relations,
+        SpkSegmentDescriptor,
+    };
+    use crate::address::WordAddress;
+    use crate::daf::{FileRecord, Summary, SummaryRecord, RECORD_SIZE};
+    use crate::error::DafError;
+
+    fn spk_summary(data_type: i32) -> Summary {
+        return Summary {
+            doubles: vec![-1.0e9, 1.0e9],
+            integers: vec![399, 3, 1, data_type, 1025, 2048],
+        };
+    }
+
+    #[test]
+    fn from_summary() {
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        assert_eq!(399, descriptor.target);
+        assert_eq!(3, descriptor.center);
+        assert_eq!(1, descriptor.frame);
+        assert_eq!(2, descriptor.data_type);
+        assert_eq!(-1.0e9, descriptor.start_et);
+        assert_eq!(1.0e9, descriptor.stop_et);
+        assert_eq!(WordAddress(1025), descriptor.start_addr);
+        assert_eq!(WordAddress(2048), descriptor.end_addr);
+    }
+
+    #[test]
+    fn implausible_type() {
+        assert_eq!(
+            Err(DafError::ImplausibleSpkType(99999)),
+            SpkSegmentDescriptor::from_summary(&spk_summary(99999))
+        );
+        assert_eq!(
+            Err(DafError::ImplausibleSpkType(0)),
+            SpkSegmentDescriptor::from_summary(&spk_summary(0))
+        );
+        assert!(SpkSegmentDescriptor::from_summary(&spk_summary(21)).is_ok());
+    }
+
+    #[test]
+    fn inverted_window() {
+        let mut summary = spk_summary(2);
+        summary.doubles.reverse();
+        assert_eq!(
+            Err(DafError::InvertedEpochWindow {
+                start: 1.0e9,
+                stop: -1.0e9
+            }),
+            SpkSegmentDescriptor::from_summary(&summary)
+        );
+
+        summary.doubles = vec![0.0, 0.0];
+        assert!(SpkSegmentDescriptor::from_summary(&summary).is_ok());
+    }
+
+    /// The first summary of a little endian DAF whose file record declares ND and NI
+    fn parsed_summary(nd: i32, ni: i32) -> Summary {
+        let mut file_record = vec![0; RECORD_SIZE];
+        file_record[8..12].copy_from_slice(&nd.to_le_bytes());
+        file_record[12..16].copy_from_slice(&ni.to_le_bytes());
+        file_record[88..96].copy_from_slice(b"LTL-IEEE");
+        let file_record = FileRecord::try_from(file_record.as_slice()).unwrap();
+
+        let mut summary_record = vec![0; RECORD_SIZE];
+        summary_record[16..24].copy_from_slice(&1.0f64.to_le_bytes());
+        let integers = (24 + 8 * nd as usize)..;
+        for (word, value) in summary_record[integers]
+            .chunks_exact_mut(4)
+            .zip([399, 3, 1, 2])
+        {
+            word.copy_from_slice(&i32::to_le_bytes(value));
+        }
+        let record = SummaryRecord::from_bytes(&summary_record, &file_record).unwrap();
+        return record.summaries[0].clone();
+    }
+
+    #[test]
+    fn shape_follows_file_record() {
+        assert!(SpkSegmentDescriptor::from_summary(&parsed_summary(2, 6)).is_ok());
+
+        // A binary PCK (ND = 2, NI = 5) misread as an SPK
+        assert_eq!(
+            Err(DafError::SummaryShapeMismatch {
+                n_double: 2,
+                n_integer: 5
+            }),
+            SpkSegmentDescriptor::from_summary(&parsed_summary(2, 5))
+        );
+        assert_eq!(
+            Err(DafError::SummaryShapeMismatch {
+                n_double: 3,
+                n_integer: 6
+            }),
+            SpkSegmentDescriptor::from_summary(&parsed_summary(3, 6))
+        );
+    }
+
+    #[test]
+    fn wrong_shape() {
+        let summary = Summary {
+            doubles: vec![-1.0e9, 1.0e9],
+            integers: vec![31006, 1, 2, 1025, 2048],
+        };
+        assert_eq!(
+            Err(DafError::SummaryShapeMismatch {
+                n_double: 2,
+                n_integer: 5
+            }),
+            SpkSegmentDescriptor::from_summary(&summary)
+        );
+    }
+
+    #[test]
+    fn duration_and_contains() {
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        assert_eq!(
+            descriptor.stop_et - descriptor.start_et,
+            descriptor.duration()
+        );
+        assert_eq!(2.0e9, descriptor.duration());
+
+        assert!(descriptor.contains(-1.0e9));
+        assert!(descriptor.contains(0.0));
+        assert!(descriptor.contains(1.0e9));
+        assert!(!descriptor.contains((-1.0e9f64).next_down()));
+        assert!(!descriptor.contains(1.0e9f64.next_up()));
+
+        assert!(descriptor.contains_within(1.0e9 + 1e-3, 1e-3));
+        assert!(descriptor.contains_within(-1.0e9 - 1e-3, 1e-3));
+        assert!(!descriptor.contains_within(1.0e9 + 1.0, 1e-3));
+    }
+
+    #[test]
+    fn provides_velocity() {
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        assert!(descriptor.provides_velocity());
+
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(13)).unwrap();
+        assert!(descriptor.provides_velocity());
+
+        // Type 4 is reserved, with no defined output
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(4)).unwrap();
+        assert!(!descriptor.provides_velocity());
+    }
+
+    #[test]
+    fn bodies_present() {
+        let earth = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        let moon = SpkSegmentDescriptor {
+            target: 301,
+            ..earth
+        };
+        let descriptors = [earth, moon, earth];
+
+        assert_eq!(
+            vec![301, 399],
+            bodies(&descriptors).into_iter().collect::<Vec<_>>()
+        );
+        assert!(has_body(&descriptors, 399));
+        assert!(has_body(&descriptors, 301));
+        assert!(!has_body(&descriptors, 499));
+
+        // The center of a segment is not one of its bodies
+        assert!(!has_body(&descriptors, 3));
+        assert!(bodies(&[]).is_empty());
+    }
+
+    #[test]
+    fn overlapping_segments() {
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        let with = |target, start_et, stop_et| SpkSegmentDescriptor {
+            target,
+            start_et,
+            stop_et,
+            ..descriptor
+        };
+
+        // Two Earth segments share 50..100; the Moon segments only meet at 100
+        let descriptors = [
+            with(399, 50.0, 200.0),
+            with(301, 0.0, 100.0),
+            with(399, 0.0, 100.0),
+            with(301, 100.0, 200.0),
+        ];
+        assert_eq!(vec![(399, (50.0, 100.0))], detect_overlaps(&descriptors));
+
+        // A segment inside another overlaps it over its whole window, and a copy fully
+        let descriptors = [
+            with(399, 0.0, 100.0),
+            with(399, 20.0, 30.0),
+            with(399, 0.0, 100.0),
+        ];
+        assert_eq!(
+            vec![
+                (399, (0.0, 100.0)),
+                (399, (20.0, 30.0)),
+                (399, (20.0, 30.0))
+            ],
+            detect_overlaps(&descriptors)
+        );
+        assert!(detect_overlaps(&[]).is_empty());
+    }
+
+    #[test]
+    fn find_by_target_center_and_type() {
+        let type_3 = SpkSegmentDescriptor::from_summary(&spk_summary(3)).unwrap();
+        let type_2 = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        let moon = SpkSegmentDescriptor {
+            target: 301,
+            ..type_2
+        };
+        let descriptors = [type_3, moon, type_2];
+
+        assert_eq!(Some(&type_2), find_segment(&descriptors, 399, 3, 2));
+        assert_eq!(Some(&type_3), find_segment(&descriptors, 399, 3, 3));
+        assert_eq!(Some(&moon), find_segment(&descriptors, 301, 3, 2));
+        assert_eq!(None, find_segment(&descriptors, 399, 0, 2));
+        assert_eq!(None, find_segment(&descriptors, 399, 3, 13));
+    }
+
+    #[test]
+    fn conflicting_frames() {
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        let with = |target, frame| SpkSegmentDescriptor {
+            target,
+            frame,
+            ..descriptor
+        };
+
+        let descriptors = [with(399, 1), with(301, 1), with(399, 17), with(399, 1)];
+        assert_eq!(vec![(399, vec![1, 17])], frame_conflicts(&descriptors));
+
+        let descriptors = [with(399, 1), with(301, 1), with(399, 1)];
+        assert!(frame_conflicts(&descriptors).is_empty());
+        assert!(frame_conflicts(&[]).is_empty());
+    }
+
+    #[test]
+    fn target_center_pairs() {
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        let with = |target, center| SpkSegmentDescriptor {
+            target,
+            center,
+            ..descriptor
+        };
+
+        // A DE-style layout: planets about the barycenter, Earth and Moon about the EMB
+        let descriptors = [
+            with(3, 0),
+            with(399, 3),
+            with(301, 3),
+            with(1, 0),
+            with(399, 3),
+        ];
+        assert_eq!(
+            vec![(1, 0), (3, 0), (301, 3), (399, 3)],
+            relations(&descriptors)
+        );
+        assert!(relations(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip_is_exact() {
+        use rand::prelude::*;
+
+        let descriptor = SpkSegmentDescriptor::from_summary(&spk_summary(2)).unwrap();
+        let mut windows = vec![
+            (-1.0e9 / 3.0, 2.0e9 / 7.0),
+            (f64::MIN, f64::MAX),
+            (-f64::MIN_POSITIVE, 5e-324),
+            (0.1 + 0.2, 0.30000000000000004_f64.next_up()),
+        ];
+        let mut rng = rand::thread_rng();
+        windows.extend((0..100).map(|_| {
+            let start = rng.gen_range(-1.0e10..1.0e10);
+            return (start, start + rng.gen_range(0.0..1.0e10));
+        }));
+
+        for (start_et, stop_et) in windows {
+            let original = SpkSegmentDescriptor {
+                start_et,
+                stop_et,
+                ..descriptor
+            };
+            let json = serde_json::to_string(&original).unwrap();
+            let back: SpkSegmentDescriptor = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                original.start_et.to_bits(),
+                back.start_et.to_bits(),
+                "{json}"
+            );
+            assert_eq!(original.stop_et.to_bits(), back.stop_et.to_bits(), "{json}");
+            assert_eq!(original, back);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Cartesian states of bodies
+
+/// Meters per kilometer
+const METERS_PER_KM: f64 = 1000.0;
+
+/// Cartesian position and velocity
+///
+/// States are stored in kilometers and kilometers per second, the native SPK units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct State {
+    /// Position (km)
+    pub position: [f64; 3],
+
+    /// Velocity (km/s)
+    pub velocity: [f64; 3],
+}
+
+impl State {
+    /// Position and velocity in SI units (m, m/s)
+    ///
+    /// The returned value holds meters; it must not be mixed with states in native units.
+    pub fn to_si(&self) -> State {
+        return State {
+            position: self.position.map(|x| x * METERS_PER_KM),
+            velocity: self.velocity.map(|v| v * METERS_PER_KM),
+        };
+    }
+
+    /// Convert a position and velocity given in SI units (m, m/s) to native units
+    pub fn from_si(si: &State) -> State {
+        return State {
+            position: si.position.map(|x| x / METERS_PER_KM),
+            velocity: si.velocity.map(|v| v / METERS_PER_KM),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+
+    const STATE: State = State {
+        position: [1.5e8, -2.0e3, 0.25],
+        velocity: [29.8, -0.5, 1.0e-3],
+    };
+
+    #[test]
+    fn to_si() {
+        let si = STATE.to_si();
+        assert_eq!([1.5e11, -2.0e6, 250.0], si.position);
+        assert_eq!([29800.0, -500.0, 1.0], si.velocity);
+    }
+
+    #[test]
+    fn from_si_round_trip() {
+        assert_eq!(STATE, State::from_si(&STATE.to_si()));
+    }
+}
@@ -0,0 +1,353 @@
+//! Types for handling time values used by the ephemeris
+
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// Seconds in a day
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// Days from 0000-03-01 (proleptic Gregorian) to 2000-01-01
+const DAYS_TO_2000: i64 = 730425;
+
+/// Offset of TT from TAI (seconds)
+const TT_MINUS_TAI: f64 = 32.184;
+
+/// Amplitude of the periodic TDB - TT term (seconds)
+const DELTET_K: f64 = 1.657e-3;
+
+/// Eccentricity of the heliocentric orbit of the Earth-Moon barycenter
+const DELTET_EB: f64 = 1.671e-2;
+
+/// Mean anomaly of the Earth-Moon barycenter at J2000 (radians) and its rate (radians/second)
+const DELTET_M: [f64; 2] = [6.239996, 1.99096871e-7];
+
+/// Leap seconds as `(year, month, TAI - UTC)`, each effective from the first of that month
+const LEAP_SECONDS: [(i32, u32, f64); 28] = [
+    (1972, 1, 10.0),
+    (1972, 7, 11.0),
+    (1973, 1, 12.0),
+    (1974, 1, 13.0),
+    (1975, 1, 14.0),
+    (1976, 1, 15.0),
+    (1977, 1, 16.0),
+    (1978, 1, 17.0),
+    (1979, 1, 18.0),
+    (1980, 1, 19.0),
+    (1981, 7, 20.0),
+    (1982, 7, 21.0),
+    (1983, 7, 22.0),
+    (1985, 7, 23.0),
+    (1988, 1, 24.0),
+    (1990, 1, 25.0),
+    (1991, 1, 26.0),
+    (1992, 7, 27.0),
+    (1993, 7, 28.0),
+    (1994, 7, 29.0),
+    (1996, 1, 30.0),
+    (1997, 7, 31.0),
+    (1999, 1, 32.0),
+    (2006, 1, 33.0),
+    (2009, 1, 34.0),
+    (2012, 7, 35.0),
+    (2015, 7, 36.0),
+    (2017, 1, 37.0),
+];
+
+/// Ephemeris time (TDB) in seconds past the J2000 epoch
+///
+/// This is a thin wrapper around `f64` so that ET values are not confused with days or Julian
+/// dates at call sites. Subtracting two `Et` values yields a duration in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Et(pub f64);
+
+impl Et {
+    /// Seconds past J2000 as a bare `f64`
+    pub fn into_inner(self) -> f64 {
+        return self.0;
+    }
+}
+
+impl From<f64> for Et {
+    fn from(seconds: f64) -> Self {
+        return Et(seconds);
+    }
+}
+
+impl Add<f64> for Et {
+    type Output = Et;
+
+    /// Offset the epoch by a duration in seconds
+    fn add(self, seconds: f64) -> Et {
+        return Et(self.0 + seconds);
+    }
+}
+
+impl Sub<Et> for Et {
+    type Output = f64;
+
+    /// Duration in seconds between two epochs
+    fn sub(self, other: Et) -> f64 {
+        return self.0 - other.0;
+    }
+}
+
+/// A UTC calendar epoch, as parsed from an ISO-8601 string such as `2024-03-14T12:00:00`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UtcEpoch {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: f64,
+}
+
+impl UtcEpoch {
+    /// Convert to ephemeris time using the built-in leap second table
+    ///
+    /// TDB - TT is the same periodic approximation SPICE applies with a leapseconds kernel, good
+    /// to a few tens of microseconds. Leap seconds announced after the table was written are not
+    /// known, and epochs before 1972 (when leap seconds began) are rejected by the parser.
+    pub fn to_et(&self) -> Et {
+        let days = days_from_civil(self.year, self.month, self.day) - DAYS_TO_2000;
+        let seconds_of_day =
+            f64::from(self.hour * 3600 + self.minute * 60) + self.second - SECONDS_PER_DAY / 2.0;
+        let utc = days as f64 * SECONDS_PER_DAY + seconds_of_day;
+
+        let tt = utc + self.tai_minus_utc() + TT_MINUS_TAI;
+        let m = DELTET_M[0] + DELTET_M[1] * tt;
+        let e = m + DELTET_EB * m.sin();
+        return Et(tt + DELTET_K * e.sin());
+    }
+
+    /// The accumulated leap seconds (TAI - UTC) in effect on this date
+    fn tai_minus_utc(&self) -> f64 {
+        return LEAP_SECONDS
+            .iter()
+            .rev()
+            .find(|(year, month, _)| (self.year, self.month) >= (*year, *month))
+            .map_or(LEAP_SECONDS[0].2, |leap| leap.2);
+    }
+}
+
+impl FromStr for UtcEpoch {
+    type Err = &'static str;
+
+    /// Parse `YYYY-MM-DD`, optionally followed by `T` (or a space) and `HH:MM[:SS[.fff]]`
+    ///
+    /// A trailing `Z` is accepted, since the epoch is always UTC.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_suffix('Z').unwrap_or(s);
+        let (date, time) = match s.split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+
+        let mut fields = date.splitn(3, '-');
+        let year: i32 = parse_field(fields.next())?;
+        let month: u32 = parse_field(fields.next())?;
+        let day: u32 = parse_field(fields.next())?;
+
+        let (hour, minute, second) = match time {
+            Some(time) => {
+                let mut fields = time.splitn(3, ':');
+                let hour: u32 = parse_field(fields.next())?;
+                let minute: u32 = parse_field(fields.next())?;
+                let second: f64 = match fields.next() {
+                    Some(second) => parse_field(Some(second))?,
+                    None => 0.0,
+                };
+                (hour, minute, second)
+            }
+            None => (0, 0, 0.0),
+        };
+
+        if year < LEAP_SECONDS[0].0 {
+            return Err("UTC epochs before 1972 are not supported");
+        }
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err("invalid calendar date");
+        }
+        let minute_length = if is_leap_second_minute(year, month, day, hour, minute) {
+            61.0
+        } else {
+            60.0
+        };
+        if hour > 23 || minute > 59 || !(0.0..minute_length).contains(&second) {
+            return Err("invalid time of day");
+        }
+
+        return Ok(UtcEpoch {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        });
+    }
+}
+
+/// Parse one numeric field of a date or time
+fn parse_field<T: FromStr>(field: Option<&str>) -> Result<T, &'static str> {
+    let field = field.ok_or("incomplete UTC epoch")?;
+    if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return Err("UTC epoch fields must be decimal numbers");
+    }
+    return field
+        .parse()
+        .map_err(|_| "UTC epoch fields must be decimal numbers");
+}
+
+/// Whether the minute ends with a leap second, i.e. has a 61st second `23:59:60`
+///
+/// Leap seconds are inserted at the end of the last day of the month before each entry in the
+/// table. The first entry is the initial offset, not an insertion.
+fn is_leap_second_minute(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> bool {
+    if hour != 23 || minute != 59 || day != days_in_month(year, month) {
+        return false;
+    }
+    let next = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    return LEAP_SECONDS[1..]
+        .iter()
+        .any(|&(year, month, _)| (year, month) == next);
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    return match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+}
+
+/// Days since 0000-03-01 of a proleptic Gregorian date
+///
+/// Counting from March puts the leap day at the end of the year, so the day of the year follows
+/// from the month with a single linear formula.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (i64::from(month) + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    return era * 146097 + day_of_era;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{days_from_civil, Et, UtcEpoch, DAYS_TO_2000};
+
+    fn seconds(et: impl Into<Et>) -> f64 {
+        return et.into().into_inner();
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(Et(12.5), Et::from(12.5));
+        assert_eq!(12.5, seconds(12.5));
+        assert_eq!(12.5, seconds(Et(12.5)));
+    }
+
+    #[test]
+    fn add_seconds() {
+        assert_eq!(Et(110.0), Et(100.0) + 10.0);
+        assert_eq!(Et(90.0), Et(100.0) + -10.0);
+    }
+
+    #[test]
+    fn sub_yields_duration() {
+        assert_eq!(86400.0, Et(86400.0) - Et(0.0));
+        assert_eq!(-5.0, Et(10.0) - Et(15.0));
+    }
+
+    fn utc_to_et(utc: &str) -> f64 {
+        return utc.parse::<UtcEpoch>().unwrap().to_et().into_inner();
+    }
+
+    #[test]
+    fn civil_days() {
+        assert_eq!(DAYS_TO_2000, days_from_civil(2000, 1, 1));
+        assert_eq!(1, days_from_civil(2000, 1, 2) - DAYS_TO_2000);
+        assert_eq!(60, days_from_civil(2000, 3, 1) - DAYS_TO_2000);
+        assert_eq!(8766, days_from_civil(2024, 1, 1) - DAYS_TO_2000);
+    }
+
+    #[test]
+    fn parse_utc() {
+        let epoch: UtcEpoch = "2024-03-14T12:34:56.5".parse().unwrap();
+        assert_eq!(
+            UtcEpoch {
+                year: 2024,
+                month: 3,
+                day: 14,
+                hour: 12,
+                minute: 34,
+                second: 56.5
+            },
+            epoch
+        );
+        assert_eq!(Ok(epoch), "2024-03-14 12:34:56.5Z".parse());
+
+        let midnight: UtcEpoch = "2024-03-14".parse().unwrap();
+        assert_eq!(
+            (0, 0, 0.0),
+            (midnight.hour, midnight.minute, midnight.second)
+        );
+
+        assert!("2016-12-31T23:59:60".parse::<UtcEpoch>().is_ok());
+        assert!("2016-12-31T23:59:60.999".parse::<UtcEpoch>().is_ok());
+        assert!("2015-06-30T23:59:60".parse::<UtcEpoch>().is_ok());
+        assert!("2024-02-29T00:00".parse::<UtcEpoch>().is_ok());
+    }
+
+    #[test]
+    fn parse_utc_invalid() {
+        for utc in [
+            "",
+            "2024-03",
+            "2024-13-01",
+            "2023-02-29",
+            "2024-03-14T24:00:00",
+            "2024-03-14T12:60",
+            "2024-03-14T12:00:61",
+            "2024-03-14T12:00:60",
+            "2017-12-31T23:59:60",
+            "2016-12-31T23:58:60",
+            "2016-12-30T23:59:60",
+            "2016-12-31T23:59:61",
+            "2024-03-14T12:00:-1",
+            "2024/03/14",
+            "1969-07-20T20:17:40",
+        ] {
+            assert!(utc.parse::<UtcEpoch>().is_err(), "{utc}");
+        }
+    }
+
+    #[test]
+    fn utc_to_et_known_values() {
+        // TAI - UTC is 32 s at J2000 and 37 s in 2024, plus the 32.184 s TT offset and TDB - TT
+        assert!((utc_to_et("2000-01-01T12:00:00") - 64.183927840).abs() < 1e-6);
+        assert!((utc_to_et("2024-03-14T12:00:00") - 763689669.185558).abs() < 1e-6);
+    }
+
+    #[test]
+    fn utc_to_et_leap_second() {
+        // The leap second at the end of 2016 makes the last minute of the year 61 seconds long
+        let before = utc_to_et("2016-12-31T23:59:59");
+        let after = utc_to_et("2017-01-01T00:00:00");
+        assert!((after - before - 2.0).abs() < 1e-6);
+
+        let leap = utc_to_et("2016-12-31T23:59:60");
+        assert!((leap - before - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,257 @@
+//! Two-body (Keplerian) orbits
+
+use std::f64::consts::TAU;
+
+/// Relative size below which eccentricity or the node vector is treated as zero
+const DEGENERACY_TOLERANCE: f64 = 1e-11;
+
+/// Osculating classical orbital elements
+///
+/// Angles are in radians, in `[0, 2 pi)` except the inclination, which is in `[0, pi]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Semi-major axis (km); negative for hyperbolic orbits and infinite for parabolic ones
+    pub semi_major_axis: f64,
+
+    /// Eccentricity
+    pub eccentricity: f64,
+
+    /// Inclination of the orbit plane to the reference plane
+    pub inclination: f64,
+
+    /// Right ascension (longitude) of the ascending node
+    ///
+    /// Zero for equatorial orbits, which have no node.
+    pub raan: f64,
+
+    /// Argument of periapsis, measured from the ascending node
+    ///
+    /// Equatorial orbits measure it from the reference x axis instead, and circular orbits
+    /// (which have no periapsis) use zero.
+    pub argument_of_periapsis: f64,
+
+    /// True anomaly, measured from periapsis
+    ///
+    /// On circular orbits this is measured from the node (or the x axis when also equatorial),
+    /// i.e. it is the argument of latitude or the true longitude.
+    pub true_anomaly: f64,
+}
+
+/// Osculating elements of a state (km, km/s) about a body with gravitational parameter `gm`
+/// (km^3/s^2)
+pub fn state_to_elements(state: [f64; 6], gm: f64) -> OrbitalElements {
+    let r = [state[0], state[1], state[2]];
+    let v = [state[3], state[4], state[5]];
+    let r_mag = norm(&r);
+    let v_mag = norm(&v);
+
+    let h = cross(&r, &v);
+    let h_mag = norm(&h);
+    let h_hat = h.map(|x| x / h_mag);
+    let node = [-h[1], h[0], 0.0];
+
+    let radial = v_mag * v_mag - gm / r_mag;
+    let r_dot_v = dot(&r, &v);
+    let e_vec: [f64; 3] = std::array::from_fn(|i| (radial * r[i] - r_dot_v * v[i]) / gm);
+    let eccentricity = norm(&e_vec);
+
+    let energy = v_mag * v_mag / 2.0 - gm / r_mag;
+    let semi_major_axis = -gm / (2.0 * energy);
+    let inclination = (h[2] / h_mag).clamp(-1.0, 1.0).acos();
+
+    let inclined = norm(&node) > DEGENERACY_TOLERANCE * h_mag;
+    let circular = eccentricity < DEGENERACY_TOLERANCE;
+
+    // Angles are measured in the orbit plane from the node, or the x axis if there is none
+    let reference = if inclined { node } else { [1.0, 0.0, 0.0] };
+    let raan = if inclined {
+        wrap(node[1].atan2(node[0]))
+    } else {
+        0.0
+    };
+    let (argument_of_periapsis, true_anomaly) = if circular {
+        (0.0, angle_between(&reference, &r, &h_hat))
+    } else {
+        (
+            angle_between(&reference, &e_vec, &h_hat),
+            angle_between(&e_vec, &r, &h_hat),
+        )
+    };
+
+    return OrbitalElements {
+        semi_major_axis,
+        eccentricity,
+        inclination,
+        raan,
+        argument_of_periapsis,
+        true_anomaly,
+    };
+}
+
+/// Angle from `a` to `b` in `[0, 2 pi)`, positive in the right-handed sense about `axis`
+fn angle_between(a: &[f64; 3], b: &[f64; 3], axis: &[f64; 3]) -> f64 {
+    return wrap(dot(&cross(a, b), axis).atan2(dot(a, b)));
+}
+
+/// Wrap an angle from `(-pi, pi]` into `[0, 2 pi)`
+fn wrap(angle: f64) -> f64 {
+    return if angle < 0.0 { angle + TAU } else { angle };
+}
+
+/// Dot product of two vectors
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    return a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+}
+
+/// Cross product `a x b`
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    return [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ];
+}
+
+/// Euclidean length of a vector
+fn norm(a: &[f64; 3]) -> f64 {
+    return dot(a, a).sqrt();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{state_to_elements, OrbitalElements};
+
+    /// Gravitational parameter of the Earth (km^3/s^2)
+    const GM_EARTH: f64 = 398600.0;
+
+    /// State of the orbit with the given elements, the inverse of `state_to_elements`
+    fn elements_to_state(el: &OrbitalElements, gm: f64) -> [f64; 6] {
+        let p = el.semi_major_axis * (1.0 - el.eccentricity * el.eccentricity);
+        let (sin_nu, cos_nu) = el.true_anomaly.sin_cos();
+        let r = p / (1.0 + el.eccentricity * cos_nu);
+        let speed = (gm / p).sqrt();
+        let perifocal = [
+            [r * cos_nu, r * sin_nu],
+            [-speed * sin_nu, speed * (el.eccentricity + cos_nu)],
+        ];
+
+        let (so, co) = el.raan.sin_cos();
+        let (sw, cw) = el.argument_of_periapsis.sin_cos();
+        let (si, ci) = el.inclination.sin_cos();
+        let p_hat = [co * cw - so * sw * ci, so * cw + co * sw * ci, sw * si];
+        let q_hat = [-co * sw - so * cw * ci, -so * sw + co * cw * ci, cw * si];
+
+        let mut state = [0.0; 6];
+        for i in 0..3 {
+            state[i] = perifocal[0][0] * p_hat[i] + perifocal[0][1] * q_hat[i];
+            state[i + 3] = perifocal[1][0] * p_hat[i] + perifocal[1][1] * q_hat[i];
+        }
+        return state;
+    }
+
+    fn assert_elements_eq(expected: &OrbitalElements, actual: &OrbitalElements) {
+        let close = |a: f64, b: f64, tol: f64| (a - b).abs() <= tol * a.abs().max(1.0);
+        assert!(
+            close(expected.semi_major_axis, actual.semi_major_axis, 1e-9),
+            "{actual:?}"
+        );
+        assert!(
+            close(expected.eccentricity, actual.eccentricity, 1e-9),
+            "{actual:?}"
+        );
+        assert!(
+            close(expected.inclination, actual.inclination, 1e-9),
+            "{actual:?}"
+        );
+        assert!(close(expected.raan, actual.raan, 1e-9), "{actual:?}");
+        assert!(
+            close(
+                expected.argument_of_periapsis,
+                actual.argument_of_periapsis,
+                1e-9
+            ),
+            "{actual:?}"
+        );
+        assert!(
+            close(expected.true_anomaly, actual.true_anomaly, 1e-9),
+            "{actual:?}"
+        );
+    }
+
+    #[test]
+    fn known_orbit() {
+        // Curtis, Orbital Mechanics for Engineering Students, Example 4.3
+        let state = [-6045.0, -3490.0, 2500.0, -3.457, 6.618, 2.533];
+        let el = state_to_elements(state, GM_EARTH);
+        assert!((el.semi_major_axis - 8788.0).abs() < 1.0);
+        assert!((el.eccentricity - 0.1712).abs() < 1e-4);
+        assert!((el.inclination.to_degrees() - 153.2).abs() < 0.05);
+        assert!((el.raan.to_degrees() - 255.3).abs() < 0.05);
+        assert!((el.argument_of_periapsis.to_degrees() - 20.07).abs() < 0.05);
+        assert!((el.true_anomaly.to_degrees() - 28.45).abs() < 0.05);
+    }
+
+    #[test]
+    fn round_trip() {
+        for (e, i) in [(0.1, 0.5), (0.7, 2.5), (1.8, 1.0)] {
+            let a = if e < 1.0 { 7000.0 } else { -20000.0 };
+            let expected = OrbitalElements {
+                semi_major_axis: a,
+                eccentricity: e,
+                inclination: i,
+                raan: 1.0,
+                argument_of_periapsis: 2.0,
+                true_anomaly: 0.3,
+            };
+            let state = elements_to_state(&expected, GM_EARTH);
+            assert_elements_eq(&expected, &state_to_elements(state, GM_EARTH));
+        }
+    }
+
+    #[test]
+    fn circular_orbit() {
+        let el = OrbitalElements {
+            semi_major_axis: 42164.0,
+            eccentricity: 0.0,
+            inclination: 0.9,
+            raan: 4.0,
+            argument_of_periapsis: 0.0,
+            true_anomaly: 1.2,
+        };
+        let state = elements_to_state(&el, GM_EARTH);
+        let out = state_to_elements(state, GM_EARTH);
+        assert_elements_eq(&el, &out);
+        assert_eq!(0.0, out.argument_of_periapsis);
+    }
+
+    #[test]
+    fn equatorial_orbit() {
+        // Without a node the periapsis is measured from the x axis
+        let el = OrbitalElements {
+            semi_major_axis: 10000.0,
+            eccentricity: 0.2,
+            inclination: 0.0,
+            raan: 0.0,
+            argument_of_periapsis: 0.8,
+            true_anomaly: 5.0,
+        };
+        let state = elements_to_state(&el, GM_EARTH);
+        let out = state_to_elements(state, GM_EARTH);
+        assert_elements_eq(&el, &out);
+        assert_eq!(0.0, out.raan);
+    }
+
+    #[test]
+    fn circular_equatorial_orbit() {
+        let r = 7000.0;
+        let speed = (GM_EARTH / r).sqrt();
+        let state = [0.0, r, 0.0, -speed, 0.0, 0.0];
+        let out = state_to_elements(state, GM_EARTH);
+        assert!((out.semi_major_axis - r).abs() < 1e-9);
+        assert!(out.eccentricity < 1e-12);
+        assert_eq!(0.0, out.inclination);
+        assert_eq!(0.0, out.raan);
+        assert_eq!(0.0, out.argument_of_periapsis);
+        assert!((out.true_anomaly - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    }
+}
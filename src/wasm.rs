@@ -0,0 +1,69 @@
+//! JavaScript bindings for parsing DAF data held in memory (e.g. from an `ArrayBuffer`)
+
+use wasm_bindgen::prelude::*;
+
+use crate::byteorder::ByteOrder;
+use crate::daf::FileRecord;
+
+/// A parsed DAF file record exposed to JavaScript
+#[wasm_bindgen]
+pub struct WasmFileRecord(FileRecord);
+
+#[wasm_bindgen]
+impl WasmFileRecord {
+    /// The identification word, e.g. `DAF/SPK`
+    #[wasm_bindgen(getter)]
+    pub fn id_word(&self) -> String {
+        return self.0.id_word_str().into_owned();
+    }
+
+    /// The internal name or description of the array file
+    #[wasm_bindgen(getter)]
+    pub fn description(&self) -> String {
+        return self.0.description_str().into_owned();
+    }
+
+    /// The number of double precision components in each array summary
+    #[wasm_bindgen(getter)]
+    pub fn n_double(&self) -> i32 {
+        return self.0.n_double;
+    }
+
+    /// The number of integer components in each array summary
+    #[wasm_bindgen(getter)]
+    pub fn n_integer(&self) -> i32 {
+        return self.0.n_integer;
+    }
+
+    /// The record number of the initial summary record in the file
+    #[wasm_bindgen(getter)]
+    pub fn forward(&self) -> i32 {
+        return self.0.forward.0;
+    }
+
+    /// The record number of the final summary record in the file
+    #[wasm_bindgen(getter)]
+    pub fn backward(&self) -> i32 {
+        return self.0.backward.0;
+    }
+
+    /// The first free address in the file
+    #[wasm_bindgen(getter)]
+    pub fn first_free(&self) -> i32 {
+        return self.0.first_free.0;
+    }
+
+    /// Whether the file data is little endian
+    #[wasm_bindgen(getter)]
+    pub fn little_endian(&self) -> bool {
+        return matches!(self.0.byte_ordering, ByteOrder::LittleEndian);
+    }
+}
+
+/// Parse the file record at the start of a DAF held in memory
+#[wasm_bindgen]
+pub fn parse_file_record(bytes: &[u8]) -> Result<WasmFileRecord, JsError> {
+    return FileRecord::try_from(bytes)
+        .map(WasmFileRecord)
+        .map_err(|e| JsError::new(&e.to_string()));
+}
@@ -0,0 +1,185 @@
+//! Arbitrary input must be rejected with an error, never a panic
+//!
+//! Each case runs the whole read path on a buffer of whole records: the file record and its
+//! derived sizes, the comment area, both directions of the summary record chain and the arrays
+//! and segment descriptors they hold.
+
+use melange::address::ByteOffset;
+use melange::ck::CkSegmentDescriptor;
+use melange::daf::{parse_comment_area, DafArray, FileRecord, SummaryRecord, RECORD_SIZE};
+use melange::spk::{find_segment, frame_conflicts, SpkSegmentDescriptor};
+use proptest::prelude::*;
+
+/// Upper bound on the summary records visited, in case the links form a cycle
+const MAX_SUMMARY_RECORDS: usize = 16;
+
+/// Run every parsing step that applies to `file`, discarding the results
+fn read_everything(file: &[u8]) {
+    let file_record = match FileRecord::try_from(file) {
+        Ok(record) => record,
+        Err(_) => return,
+    };
+    let _ = file_record.id_word_str();
+    let _ = file_record.description_str();
+    let _ = file_record.ephemeris_name();
+    let _ = file_record.single_summary_size();
+    let _ = file_record.n_character();
+    let _ = file_record.summaries_per_record();
+    let _ = file_record.num_records();
+    let _ = file_record.expected_min_bytes();
+    let _ = file_record.n_reserved_records();
+    let _ = file_record.requires_byte_swap();
+    for record in [file_record.forward, file_record.backward] {
+        let _ = record.first_word().record();
+        let _ = record.byte_offset().and_then(ByteOffset::as_usize);
+    }
+    let _ = file_record.first_free.record().first_word();
+    let _ = file_record
+        .first_free
+        .byte_offset()
+        .and_then(ByteOffset::as_usize);
+
+    let n_comment = file_record.n_comment_records() as usize;
+    let comments_end = (RECORD_SIZE + n_comment.saturating_mul(RECORD_SIZE)).min(file.len());
+    let _ = parse_comment_area(&file[RECORD_SIZE..comments_end]);
+
+    let mut descriptors = Vec::new();
+    for forward in [true, false] {
+        let mut record = if forward {
+            file_record.forward
+        } else {
+            file_record.backward
+        };
+        for _ in 0..MAX_SUMMARY_RECORDS {
+            // A link of zero (or less) names no record and ends the chain
+            let offset = record.byte_offset().and_then(ByteOffset::as_usize);
+            let bytes = match offset.and_then(|offset| file.get(offset..)) {
+                Some(bytes) => bytes,
+                None => break,
+            };
+            let summary_record = match SummaryRecord::from_bytes(bytes, &file_record) {
+                Ok(summary_record) => summary_record,
+                Err(_) => break,
+            };
+            for summary in &summary_record.summaries {
+                let _ = CkSegmentDescriptor::from_summary(summary);
+                if let Ok(descriptor) = SpkSegmentDescriptor::from_summary(summary) {
+                    let _ = descriptor.duration();
+                    let _ = descriptor.contains(0.0);
+                    let _ = descriptor.provides_velocity();
+                    descriptors.push(descriptor);
+                }
+                if let Ok(array) = DafArray::from_summary(summary) {
+                    let _ = array.doubles(file, file_record.byte_ordering);
+                }
+            }
+            record = if forward {
+                summary_record.next
+            } else {
+                summary_record.previous
+            };
+        }
+    }
+
+    let _ = frame_conflicts(&descriptors);
+    if let Some(descriptor) = descriptors.first() {
+        let _ = find_segment(&descriptors, descriptor.target, descriptor.center, 2);
+    }
+}
+
+/// Integers biased toward the values that break unchecked arithmetic
+fn edge_i32() -> impl Strategy<Value = i32> {
+    return prop_oneof![
+        Just(i32::MIN),
+        Just(i32::MIN + 1),
+        Just(-1),
+        Just(0),
+        Just(1),
+        Just(i32::MAX - 1),
+        Just(i32::MAX),
+        -2..8i32,
+        any::<i32>(),
+    ];
+}
+
+/// Whole records of arbitrary bytes
+fn arbitrary_file() -> impl Strategy<Value = Vec<u8>> {
+    return (1..6usize).prop_flat_map(|n| prop::collection::vec(any::<u8>(), n * RECORD_SIZE));
+}
+
+/// A file record with the given integer fields and arbitrary text fields
+#[derive(Debug, Clone)]
+struct Header {
+    n_double: i32,
+    n_integer: i32,
+    forward: i32,
+    backward: i32,
+    first_free: i32,
+}
+
+fn header() -> impl Strategy<Value = Header> {
+    // Mostly valid layouts, since the file record rejects the rest before anything else is read
+    let layout = prop_oneof![3 => (0..4i32, 2..8i32), 1 => (edge_i32(), edge_i32())];
+    let links = (edge_i32(), edge_i32(), edge_i32());
+    return (layout, links).prop_map(|((n_double, n_integer), (forward, backward, first_free))| {
+        return Header {
+            n_double,
+            n_integer,
+            forward,
+            backward,
+            first_free,
+        };
+    });
+}
+
+/// The contents of a summary record: next and previous links, a count, then the summary words
+fn summary_record() -> impl Strategy<Value = (i32, i32, u8, Vec<i32>)> {
+    let words = prop::collection::vec(edge_i32(), 250);
+    return (edge_i32(), edge_i32(), 0..40u8, words);
+}
+
+/// Arbitrary records behind a file record that parses, so the later steps are reached
+///
+/// The summary record that `forward` points to (if it is in the file) gets plausible control
+/// items, with its links and summary integers drawn from the edge values.
+fn seeded_file() -> impl Strategy<Value = Vec<u8>> {
+    let strategy = (arbitrary_file(), header(), summary_record());
+    return strategy.prop_map(|(mut bytes, h, (next, previous, nsum, words))| {
+        bytes[0..8].copy_from_slice(b"DAF/SPK ");
+        bytes[8..12].copy_from_slice(&h.n_double.to_le_bytes());
+        bytes[12..16].copy_from_slice(&h.n_integer.to_le_bytes());
+        bytes[76..80].copy_from_slice(&h.forward.to_le_bytes());
+        bytes[80..84].copy_from_slice(&h.backward.to_le_bytes());
+        bytes[84..88].copy_from_slice(&h.first_free.to_le_bytes());
+        bytes[88..96].copy_from_slice(b"LTL-IEEE");
+
+        let start = (h.forward.max(1) as usize - 1) * RECORD_SIZE;
+        if let Some(record) = bytes.get_mut(start..start + RECORD_SIZE) {
+            // Links must be non-negative integers to pass the control item check
+            record[0..8].copy_from_slice(&f64::from(next.max(0)).to_le_bytes());
+            record[8..16].copy_from_slice(&f64::from(previous.max(0)).to_le_bytes());
+            record[16..24].copy_from_slice(&f64::from(nsum).to_le_bytes());
+            for (word, value) in record[24..].chunks_exact_mut(4).zip(words) {
+                word.copy_from_slice(&value.to_le_bytes());
+            }
+        }
+        return bytes;
+    });
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_do_not_panic(file in arbitrary_file()) {
+        read_everything(&file);
+    }
+
+    #[test]
+    fn seeded_bytes_do_not_panic(file in seeded_file()) {
+        read_everything(&file);
+    }
+
+    #[test]
+    fn short_buffers_do_not_panic(file in prop::collection::vec(any::<u8>(), 0..RECORD_SIZE)) {
+        read_everything(&file);
+    }
+}